@@ -5,7 +5,9 @@ fn main () -> Result<(), Box<dyn std::error::Error>> {
         &[
             "proto/ping.proto",
             "proto/transaction.proto",
-            "proto/block.proto"
+            "proto/block.proto",
+            "proto/attestation.proto",
+            "proto/name.proto"
         ],
         &["proto/"],
     )?;
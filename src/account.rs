@@ -1,4 +1,4 @@
-use crate::constants::BLOCK_ADDRESS_SIZE;
+use crate::constants::{BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE};
 
 // Account as viewed by the blockchain
 #[derive(Clone, Debug)]
@@ -13,6 +13,12 @@ pub struct Account {
     is_validator: bool,
     // total amount staked for the account
     stake: u64,
+    // total amount of revoked stake that is still locked in the unbonding queue
+    unbonding: u64,
+    // total amount the account has delegated to validators
+    delegated: u64,
+    // public key the account has been rotated to, None while it is still controlled by the key its address was generated from
+    public_key: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>,
 }
 
 impl Account {
@@ -22,7 +28,10 @@ impl Account {
             balance: 0,
             nonce: 0,
             is_validator: false,
-            stake: 0
+            stake: 0,
+            unbonding: 0,
+            delegated: 0,
+            public_key: None
         }
     }
 
@@ -69,4 +78,36 @@ impl Account {
     pub fn get_stake(&self) -> u64 {
         self.stake
     }
+
+    pub fn increase_unbonding(&mut self, amount: u64) {
+        self.unbonding += amount
+    }
+
+    pub fn decrease_unbonding(&mut self, amount: u64) {
+        self.unbonding -= amount
+    }
+
+    pub fn get_unbonding(&self) -> u64 {
+        self.unbonding
+    }
+
+    pub fn increase_delegated(&mut self, amount: u64) {
+        self.delegated += amount
+    }
+
+    pub fn decrease_delegated(&mut self, amount: u64) {
+        self.delegated -= amount
+    }
+
+    pub fn get_delegated(&self) -> u64 {
+        self.delegated
+    }
+
+    pub fn set_public_key(&mut self, public_key: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>) {
+        self.public_key = public_key
+    }
+
+    pub fn get_public_key(&self) -> Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> {
+        self.public_key
+    }
 }
\ No newline at end of file
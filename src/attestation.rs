@@ -0,0 +1,59 @@
+use bincode::Options;
+use k256::ecdsa::Signature;
+use sha2::{Sha256, Digest};
+
+use crate::constants::COMPRESSED_PUBLIC_KEY_SIZE;
+
+// A validator's signed vote that a checkpoint block is part of the canonical chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attestation {
+    // height of the attested checkpoint block
+    block_height: u64,
+    // header hash of the attested checkpoint block
+    block_hash: [u8; 32],
+    // public key of the attesting validator
+    public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE],
+    // sign(sha256(block_height + block_hash))
+    signature: Signature,
+}
+
+impl Attestation {
+    pub fn new(block_height: u64, block_hash: [u8; 32], public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE], signature: Signature) -> Self {
+        Self {
+            block_height,
+            block_hash,
+            public_key,
+            signature,
+        }
+    }
+
+    pub fn get_block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    pub fn get_block_hash(&self) -> [u8; 32] {
+        self.block_hash
+    }
+
+    pub fn get_public_key(&self) -> [u8; COMPRESSED_PUBLIC_KEY_SIZE] {
+        self.public_key
+    }
+
+    pub fn get_signature(&self) -> Signature {
+        self.signature
+    }
+
+    pub fn serialize_hash_attestation_metadata(block_height: u64, block_hash: [u8; 32]) -> Vec<u8> {
+        // serialize the attested block height and hash, this is what the validator signs
+        let serialized_attestation_metadata = bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize(&(block_height, block_hash)).unwrap();
+
+        // sha256(serialized attestation metadata)
+        let mut sha256_hasher: Sha256 = Sha256::new();
+        sha256_hasher.update(serialized_attestation_metadata);
+        sha256_hasher.finalize().to_vec()
+    }
+}
\ No newline at end of file
@@ -1,31 +1,39 @@
+use std::fmt;
 use std::mem;
 
 use bincode::{Options, ErrorKind};
-use k256::ecdsa::Signature;
-use serde::{Serialize, Deserialize};
+use k256::ecdsa;
+use serde::{Serialize, Serializer, Deserialize, Deserializer, ser::SerializeTuple, de::{self, Visitor, SeqAccess}};
+use serde_big_array::Array;
 use sha2::{Sha256, Digest};
 
+use crate::signature::Signature;
 use crate::transaction::Transaction;
 
+use crate::constants::{BLOCK_FORMAT_VERSION_MASK, CHAIN_ID_BLOCK_VERSION, SCHNORR_BLOCK_VERSION, VRF_BLOCK_VERSION, VRF_PROOF_SIZE};
+
 // ToDo: May need to remove transaction_count variable, not because its uneeded, but because during serialization serde already adds a transaction count before the transaction vector
 // This duplicates the transaction count in the serialized structure. Probably should have a customized serialization function, so its not just implicity there
 // but thats extra work thats probably not needed at this time
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Block {
     block_size: u32,
     block_header: BlockHeader,
     //transaction_count: u32,
     transactions: Vec<Transaction>,
+    // proposer's signature, only serialized with its signature scheme for block format versions >= SCHNORR_BLOCK_VERSION
     signature: Signature,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BlockHeader {
     version: u32,
     prev_hash: [u8; 32],
     merkle_root: [u8; 32],
     timestamp: u64,
+    // proposer's vrf proof over the previous randomness beacon, only serialized for block format versions >= VRF_BLOCK_VERSION
+    vrf_proof: [u8; VRF_PROOF_SIZE],
 }
 
 #[derive(Debug)]
@@ -40,7 +48,7 @@ struct MerkleNode {
 }
 
 impl Block {
-    pub fn new(version: u32, prev_hash: [u8; 32], timestamp: u64, transactions: &Vec<Transaction>, signature: Signature) -> Self {
+    pub fn new(version: u32, prev_hash: [u8; 32], timestamp: u64, vrf_proof: [u8; VRF_PROOF_SIZE], transactions: &Vec<Transaction>, signature: Signature) -> Self {
         // calculate the merkle root
         let merkle_root:[u8; 32] = BlockHeader::calculate_merkle_root(transactions.clone()).try_into().unwrap();
         // create the block header
@@ -49,6 +57,7 @@ impl Block {
             prev_hash,
             merkle_root,
             timestamp,
+            vrf_proof,
         };
 
         // get the transaction count
@@ -108,6 +117,15 @@ impl Block {
         self.block_header.serialize_hash_block_header()
     }
 
+    pub fn serialize_hash_block_signing_payload(&self, chain_id: [u8; 32]) -> Vec<u8> {
+        self.block_header.serialize_hash_block_signing_payload(chain_id)
+    }
+
+    pub fn get_weight(&self) -> u64 {
+        // the block space used by the transactions, a batch transaction counts once for each of its outputs
+        self.transactions.iter().map(|transaction| transaction.get_weight()).sum()
+    }
+
     pub fn get_timesamp(&self) -> u64 {
         self.block_header.timestamp
     }
@@ -123,17 +141,32 @@ impl Block {
     pub fn get_version(&self) -> u32 {
         self.block_header.version
     }
+
+    pub fn get_format_version(&self) -> u32 {
+        self.block_header.get_format_version()
+    }
+
+    pub fn get_vrf_proof(&self) -> [u8; VRF_PROOF_SIZE] {
+        self.block_header.vrf_proof
+    }
 }
 
 impl BlockHeader {
-    pub fn new(version: u32, prev_hash: [u8; 32], merkle_root: [u8; 32], timestamp: u64) -> Self {
+    pub fn new(version: u32, prev_hash: [u8; 32], merkle_root: [u8; 32], timestamp: u64, vrf_proof: [u8; VRF_PROOF_SIZE]) -> Self {
         Self {
             version,
             prev_hash,
             merkle_root,
-            timestamp
+            timestamp,
+            vrf_proof
         }
     }
+
+    pub fn get_format_version(&self) -> u32 {
+        // the low bits of the version are the block format version, the rest are deployment signals
+        self.version & *BLOCK_FORMAT_VERSION_MASK
+    }
+
     // ToDo: This function doesn't ever create a full merkle tree, it creates each level of a merkle tree sequentially and returns just the final merkle root
     // To simplify transaction validation for light nodes (which don't and probably won't ever exist on block) a true merkle tree would be needed so that a merkle path can be used to validate single transactions
     pub fn calculate_merkle_root(transactions: Vec<Transaction>) -> Vec<u8> {
@@ -141,6 +174,7 @@ impl BlockHeader {
 
         for transaction in transactions {
             // create merkle nodes from transactions with None as parent (for now)
+            // the leaves are hashes of the full transactions, not transaction ids, so the merkle root commits to the signatures too and existing blocks keep their merkle roots
             let tx_hash = transaction.serialize_hash_tx();
             merkle_nodes.push(
                 MerkleNode {
@@ -224,6 +258,19 @@ impl BlockHeader {
         Self::hash_serialized_block_header(serialized_block_header)
     }
 
+    pub fn serialize_hash_block_signing_payload(&self, chain_id: [u8; 32]) -> Vec<u8> {
+        // the message the block proposer signs, blocks of CHAIN_ID_BLOCK_VERSION or later sign sha256(block header hash + chain id)
+        let hashed_serialized_block_header = self.serialize_hash_block_header();
+        if self.get_format_version() < *CHAIN_ID_BLOCK_VERSION {
+            return hashed_serialized_block_header
+        }
+
+        let mut sha256_hasher: Sha256 = Sha256::new();
+        sha256_hasher.update(hashed_serialized_block_header);
+        sha256_hasher.update(chain_id);
+        sha256_hasher.finalize().to_vec()
+    }
+
     pub fn from(raw: Vec<u8>) -> Result<Self, Box<ErrorKind>> {
         bincode::DefaultOptions::new()
             .allow_trailing_bytes()
@@ -238,4 +285,102 @@ impl BlockHeader {
         sha256_hasher.update(serialized_block_header);
         sha256_hasher.finalize().to_vec()
     }
+}
+
+// blocks are serialized as a tuple so that the signature scheme is only present for versions that support it,
+// this keeps the serialization of earlier blocks, which can only be signed with ECDSA, unchanged
+impl Serialize for Block {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(4)?;
+        tuple.serialize_element(&self.block_size)?;
+        tuple.serialize_element(&self.block_header)?;
+        tuple.serialize_element(&self.transactions)?;
+        if self.get_format_version() >= *SCHNORR_BLOCK_VERSION {
+            tuple.serialize_element(&self.signature)?;
+        } else {
+            tuple.serialize_element(&Array(self.signature.to_bytes()))?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Block {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlockVisitor;
+
+        impl<'de> Visitor<'de> for BlockVisitor {
+            type Value = Block;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a serialized block")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Block, A::Error> {
+                let block_size: u32 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let block_header: BlockHeader = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let transactions: Vec<Transaction> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                // only read the signature scheme if the version supports it
+                let signature = if block_header.get_format_version() >= *SCHNORR_BLOCK_VERSION {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(3, &self))?
+                } else {
+                    let signature: ecdsa::Signature = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                    Signature::Ecdsa(signature)
+                };
+
+                Ok(Block::from_parts(block_size, block_header, transactions, signature))
+            }
+        }
+
+        deserializer.deserialize_tuple(4, BlockVisitor)
+    }
+}
+
+// block headers are serialized as a tuple so that the vrf proof is only present for versions that support it,
+// this keeps the serialization (and therefore the hash) of the version 1 genesis block unchanged
+impl Serialize for BlockHeader {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let has_vrf_proof = self.get_format_version() >= *VRF_BLOCK_VERSION;
+        let mut tuple = serializer.serialize_tuple(if has_vrf_proof { 5 } else { 4 })?;
+        tuple.serialize_element(&self.version)?;
+        tuple.serialize_element(&self.prev_hash)?;
+        tuple.serialize_element(&self.merkle_root)?;
+        tuple.serialize_element(&self.timestamp)?;
+        if has_vrf_proof {
+            tuple.serialize_element(&Array(self.vrf_proof))?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockHeader {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BlockHeaderVisitor;
+
+        impl<'de> Visitor<'de> for BlockHeaderVisitor {
+            type Value = BlockHeader;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a serialized block header")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BlockHeader, A::Error> {
+                let version: u32 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let prev_hash: [u8; 32] = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let merkle_root: [u8; 32] = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let timestamp: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                // only read the vrf proof if the version supports it
+                let vrf_proof = if version & *BLOCK_FORMAT_VERSION_MASK >= *VRF_BLOCK_VERSION {
+                    let vrf_proof: Array<u8, VRF_PROOF_SIZE> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(4, &self))?;
+                    vrf_proof.0
+                } else {
+                    [0x00; VRF_PROOF_SIZE]
+                };
+
+                Ok(BlockHeader::new(version, prev_hash, merkle_root, timestamp, vrf_proof))
+            }
+        }
+
+        // the length is the maximum number of fields, the visitor stops early for older versions
+        deserializer.deserialize_tuple(5, BlockHeaderVisitor)
+    }
 }
\ No newline at end of file
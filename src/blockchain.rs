@@ -1,19 +1,31 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use k256::PublicKey;
 use sha2::{Sha256, Digest};
 
 use crate::account::Account;
+use crate::attestation::Attestation;
 use crate::block::Block;
-use crate::transaction::Transaction;
+use crate::config::BlockchainConfig;
+use crate::deployment::DeploymentState;
+use crate::epoch::Epoch;
+use crate::htlc::Htlc;
+use crate::name_record::NameRecord;
+use crate::payment_channel::PaymentChannel;
+use crate::supply::Supply;
+use crate::transaction::{Transaction, TxData};
+use crate::tx_format::TxFormat;
+use crate::unbonding_entry::UnbondingEntry;
 use crate::validator_account::ValidatorAccount;
 use crate::verification_engine;
+use crate::vrf;
 use crate::wallet::Wallet;
 
-use crate::constants::{BLOCK_ADDRESS_SIZE, BOOTSTRAPPING_PHASE_BLOCK_HEIGHT, COMPRESSED_PUBLIC_KEY_SIZE, GENESIS_BLOCK, LOOSE_CHANGE_RECIPIENT, MINIMUM_STAKING_AMOUNT, VALIDATOR_ENABLE_RECIPIENT};
+use crate::constants::{BASE_FEE_CHANGE_DENOMINATOR, BLOCK_ADDRESS_SIZE, BLOCK_VERSION, BOOTSTRAPPING_PHASE_BLOCK_HEIGHT, BURN_RECIPIENT, CHAIN_ID_BLOCK_VERSION, CHANNEL_DISPUTE_PERIOD, CHANNEL_RECIPIENT, CHECKPOINT_INTERVAL, COMPRESSED_PUBLIC_KEY_SIZE, DATA_TRANSACTION_VERSION, DEPLOYMENTS, DEPLOYMENT_THRESHOLD, DEPLOYMENT_WINDOW, EPOCH_LENGTH, GENESIS_BLOCK, HTLC_RECIPIENT, LOOSE_CHANGE_RECIPIENT, LOOSE_CHANGE_RECYCLE_DENOMINATOR, MAX_NAME_SIZE, MAX_TRANSACTIONS_PER_BLOCK, MAX_VALIDATOR_COMMISSION, MINIMUM_BASE_FEE, MINIMUM_STAKING_AMOUNT, MINIMUM_VALIDATOR_ENABLE_FEE, NAME_REGISTRATION_PERIOD, REPLACEMENT_FEE_INCREASE_DENOMINATOR, SCHNORR_BLOCK_VERSION, TRUSTED_CHECKPOINTS, UNBONDING_PERIOD, VALIDATOR_ENABLE_RECIPIENT, VALIDATOR_PROBATION_PERIOD, VRF_BLOCK_VERSION};
 
 #[derive(Debug, Clone)]
 pub struct Blockchain {
+    config: BlockchainConfig,
     // ToDo: limit total blocks stored in memory
     // vector of most recent x blocks
     blocks: Vec<Block>,
@@ -21,14 +33,50 @@ pub struct Blockchain {
     accounts: HashMap<[u8; BLOCK_ADDRESS_SIZE], Account>,
     // vector of all validators on the blockchain
     validators: Vec<ValidatorAccount>,
-    // mempool hashmap of accounts and all of the transactions they have added to the mempool sorted by nonce
-    mempool: HashMap<[u8; COMPRESSED_PUBLIC_KEY_SIZE], Vec<Transaction>>,
+    // validator set snapshot of every epoch, the last epoch is used to choose block proposers
+    epochs: Vec<Epoch>,
+    // mempool hashmap of account addresses and all of the transactions they have added to the mempool sorted by nonce
+    mempool: HashMap<[u8; BLOCK_ADDRESS_SIZE], Vec<Transaction>>,
+    // queue of revoked stake waiting to be released back to account balances
+    unbonding_queue: Vec<UnbondingEntry>,
+    // hash time-locked transfers that haven't been claimed or refunded yet, by the id of the transaction that locked them
+    htlcs: HashMap<[u8; 32], Htlc>,
+    // payment channels that haven't been settled yet, by the id of the transaction that opened them
+    payment_channels: HashMap<[u8; 32], PaymentChannel>,
+    // account addresses of the public keys accounts have been rotated to, keys that haven't been rotated to control the address generated from them
+    rotated_keys: HashMap<[u8; COMPRESSED_PUBLIC_KEY_SIZE], [u8; BLOCK_ADDRESS_SIZE]>,
+    // addresses generated from the keys in rotated_keys, no key can spend from them while their key controls another account
+    rotated_key_addresses: HashSet<[u8; BLOCK_ADDRESS_SIZE]>,
+    // registered names and the addresses they resolve to, expired names are kept until they are registered again
+    names: HashMap<[u8; MAX_NAME_SIZE], NameRecord>,
+    // number of validator enable transactions applied from the block being added, reset once the whole block is applied
+    validator_enables: usize,
+    // validator attestations for each checkpoint block height
+    attestations: HashMap<u64, Vec<Attestation>>,
+    // height of the most recent final checkpoint, blocks at or below this height can never be replaced
+    finalized_height: u64,
+    // minimum fee every transaction in the next block must pay, this part of the fee is burned
+    base_fee: u64,
+    // state of every deployment in DEPLOYMENTS and the block height from which it is in that state
+    deployment_states: Vec<(DeploymentState, u64)>,
+    // loose change the next block's coinbase transaction can recycle on top of the block subsidy
+    loose_change_reward: u64,
+    // total amount issued by coinbase transactions, recycled loose change was already issued so it isn't counted again
+    issued: u64,
+    // supply breakdown recorded after every block
+    supply_history: Vec<Supply>,
+    // trusted (block height, block header hash) checkpoints this blockchain must contain
+    trusted_checkpoints: Vec<(u64, [u8; 32])>,
+    // randomness beacon mixed from every block's vrf output, seeds the choice of the next block proposer
+    randomness_beacon: [u8; 32],
+    // genesis block header hash, identifies the network transactions and blocks are signed for
+    chain_id: [u8; 32],
     // the current blockheight
     block_height: u64
 }
 
 impl Blockchain {
-    pub fn new() -> Self {
+    pub fn new(config: BlockchainConfig) -> Self {
         // create genesis block
         let blocks: Vec<Block> = vec![];
 
@@ -38,31 +86,114 @@ impl Blockchain {
         // create validator set
         let validators: Vec<ValidatorAccount> = vec![];
 
+        // create epoch history, the first epoch is snapshotted when the genesis block is added
+        let epochs: Vec<Epoch> = vec![];
+
         // create mempool hashmap
-        let mempool: HashMap<[u8; COMPRESSED_PUBLIC_KEY_SIZE], Vec<Transaction>> = HashMap::new();
+        let mempool: HashMap<[u8; BLOCK_ADDRESS_SIZE], Vec<Transaction>> = HashMap::new();
+
+        // create unbonding queue
+        let unbonding_queue: Vec<UnbondingEntry> = vec![];
+
+        // create hash time-locked transfer registry
+        let htlcs: HashMap<[u8; 32], Htlc> = HashMap::new();
+
+        // create payment channel registry
+        let payment_channels: HashMap<[u8; 32], PaymentChannel> = HashMap::new();
+
+        // create rotated key registry
+        let rotated_keys: HashMap<[u8; COMPRESSED_PUBLIC_KEY_SIZE], [u8; BLOCK_ADDRESS_SIZE]> = HashMap::new();
+        let rotated_key_addresses: HashSet<[u8; BLOCK_ADDRESS_SIZE]> = HashSet::new();
+
+        // create name registry
+        let names: HashMap<[u8; MAX_NAME_SIZE], NameRecord> = HashMap::new();
+
+        // no block is being added yet
+        let validator_enables = 0;
+
+        // create checkpoint attestations hashmap
+        let attestations: HashMap<u64, Vec<Attestation>> = HashMap::new();
+
+        // the genesis block is always final
+        let finalized_height = 0;
+
+        // the base fee starts at its minimum
+        let base_fee = *MINIMUM_BASE_FEE;
+
+        // every deployment starts out defined
+        let deployment_states: Vec<(DeploymentState, u64)> = vec![(DeploymentState::Defined, 0); DEPLOYMENTS.len()];
+
+        // there is no loose change to recycle until a block without a coinbase transaction is added
+        let loose_change_reward = 0;
+
+        // nothing is issued until the genesis block's coinbase transaction is applied
+        let issued = 0;
+
+        // create supply history, the first supply is recorded when the genesis block is added
+        let supply_history: Vec<Supply> = vec![];
+
+        // trusted checkpoints are set when the network's genesis block is added, local blockchains don't have any
+        let trusted_checkpoints: Vec<(u64, [u8; 32])> = vec![];
+
+        // the randomness beacon is set when the genesis block is added
+        let randomness_beacon = [0x00; 32];
+
+        // the chain id is set when the genesis block is added
+        let chain_id = [0x00; 32];
 
         // initialize block height at 0
         let block_height = 0;
 
         // create blockchain object
         Self {
+            config,
             blocks,
             accounts,
             validators,
+            epochs,
             mempool,
+            unbonding_queue,
+            htlcs,
+            payment_channels,
+            rotated_keys,
+            rotated_key_addresses,
+            names,
+            validator_enables,
+            attestations,
+            finalized_height,
+            base_fee,
+            deployment_states,
+            loose_change_reward,
+            issued,
+            supply_history,
+            trusted_checkpoints,
+            randomness_beacon,
+            chain_id,
             block_height,
         }
     }
 
     pub fn add_genesis_block(&mut self) {
+        // the network's checkpoints and any extra checkpoints from the config file
+        self.trusted_checkpoints = TRUSTED_CHECKPOINTS.to_vec();
+        self.trusted_checkpoints.extend(self.config.get_trusted_checkpoints());
+
         self.add_local_genesis_block(&Block::from(GENESIS_BLOCK.to_vec()).unwrap());
     }
 
     pub fn add_local_genesis_block(&mut self, genesis_block: &Block) {
         // push the genesis block
         self.blocks.push(genesis_block.clone());
-        // update the chain with the genesis block transactions
-        self.update_chain(&genesis_block);
+        // update the chain with the genesis block transactions, the genesis block has no proposer
+        self.update_chain(genesis_block, None);
+        // the genesis block's header hash is the initial randomness beacon
+        self.randomness_beacon = genesis_block.serialize_hash_block_header().try_into().unwrap();
+        // the genesis block's header hash is also the chain id, every local blockchain has its own genesis block and so its own chain id
+        self.chain_id = self.randomness_beacon;
+        // the genesis block's validators are the validators of the first epoch
+        self.snapshot_epoch();
+        // record the initial supply
+        self.record_supply();
     }
 
     pub fn add_block(self, block: &Block) -> (bool, Blockchain) {
@@ -76,40 +207,102 @@ impl Blockchain {
             return (false, self)
         }
 
+        // get the validator that proposed the block so their delegators can share in the block rewards
+        let proposer = verification_engine::get_block_proposer_public_key(block, &new_blockchain);
+
         // add block to blockchain
         new_blockchain.blocks.push(block.clone());
 
         // update chain state
-        if !new_blockchain.update_chain(block) {
+        if !new_blockchain.update_chain(block, proposer) {
+            return (false, self)
+        }
+
+        // mix the block's vrf output into the randomness beacon
+        if !new_blockchain.update_randomness_beacon(block) {
             return (false, self)
         }
 
+        // adjust the base fee for the next block to how full this block was
+        new_blockchain.update_base_fee(block);
+
+        // set the loose change the next block can recycle, this is fixed for the whole block so the coinbase transaction stays valid as the block is applied
+        new_blockchain.update_loose_change_reward();
+
         // increment the block height
         new_blockchain.increase_block_height();
 
+        // drop transactions that can no longer be added to a block from the mempool
+        new_blockchain.remove_expired_transactions_mempool(block.get_timesamp());
+
+        // release any revoked stake that has finished unbonding at this block height
+        new_blockchain.release_unbonded_stake();
+
+        // settle any payment channel whose dispute period has ended at this block height
+        new_blockchain.settle_closed_payment_channels();
+
         // once the BOOBOOTSTRAPPING_PHASE_BLOCK_HEIGHT has been reached, all validators staking less than MINIMUM_STAKING_AMOUNT need to be unstaked
         if new_blockchain.get_block_height() == *BOOTSTRAPPING_PHASE_BLOCK_HEIGHT {
             // unstake all validators staking less than MINIMUM_STAKING_AMOUNT
             new_blockchain.bootstrapping_phase_end()
         }
 
+        // at the end of each deployment window move deployments to their next state
+        if new_blockchain.get_block_height().is_multiple_of(*DEPLOYMENT_WINDOW) {
+            new_blockchain.update_deployment_states();
+        }
+
+        // at the end of each epoch freeze the validator set used to choose block proposers for the next epoch
+        if new_blockchain.get_block_height().is_multiple_of(*EPOCH_LENGTH) {
+            new_blockchain.snapshot_epoch();
+        }
+
+        // record the supply breakdown at this block height
+        new_blockchain.record_supply();
+
+        // in verification mode make sure that no amount was created or destroyed by the block
+        if new_blockchain.config.get_verify_supply() && !new_blockchain.is_supply_conserved() {
+            println!("Supply invariant violated by block at height {}, rejecting block", new_blockchain.get_block_height());
+            println!();
+            return (false, self)
+        }
+
         (true, new_blockchain)
     }
 
     pub fn add_transaction_mempool(&mut self, transaction: &Transaction) -> bool {
         // verify the received transaction
         if verification_engine::verify_transaction(transaction, None, self) {
+            // a verified transaction always has a sender address
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                None => return false
+            };
+
+            // get the fee a transaction replacing a pending one must pay before borrowing the mempool
+            let replacement_fee = self.mempool.get(&sender_address)
+                .and_then(|transaction_vec| transaction_vec.iter().find(|pending| pending.nonce == transaction.nonce))
+                .map(|pending| self.get_minimum_replacement_fee(pending));
+
             // insert the transaction into the account nonce sorted mempool hashmap
-            match self.mempool.get_mut(&transaction.sender) {
+            match self.mempool.get_mut(&sender_address) {
                 Some(transaction_vec) => {
+                    // a transaction with the same nonce as a pending one replaces it if it pays a sufficiently higher fee
+                    if let Some(replacement_fee) = replacement_fee {
+                        if transaction.fee < replacement_fee {
+                            return false
+                        }
+                        transaction_vec.retain(|pending| pending.nonce != transaction.nonce);
+                    }
+
                     // add the transcation to the account/vec<transaction> hashmap
                     transaction_vec.push(transaction.clone());
                     // sort the accounts vec<transaction> by account nonce
-                    self.mempool.get_mut(&transaction.sender).unwrap().sort();
+                    self.mempool.get_mut(&sender_address).unwrap().sort();
                 },
                 None => {
                     // create new account/vec<transaction> entry in the mempool
-                    self.mempool.insert(transaction.sender, vec![transaction.clone()]);
+                    self.mempool.insert(sender_address, vec![transaction.clone()]);
                 }
             }
         } else {
@@ -119,8 +312,49 @@ impl Blockchain {
         true
     }
 
+    pub fn add_attestation(&mut self, attestation: &Attestation) -> bool {
+        // verify the received attestation
+        if !verification_engine::verify_attestation(attestation, self) {
+            return false
+        }
+
+        let checkpoint_attestations = self.attestations.entry(attestation.get_block_height()).or_default();
+
+        // only one attestation per validator per checkpoint
+        if checkpoint_attestations.iter().any(|checkpoint_attestation| checkpoint_attestation.get_public_key() == attestation.get_public_key()) {
+            return false
+        }
+
+        checkpoint_attestations.push(attestation.clone());
+
+        // the checkpoint becomes final once validators holding 2/3 of the stake have attested to it
+        let (attested_weight, total_weight) = self.get_attested_weight(attestation.get_block_height());
+        if total_weight != 0 && attested_weight * 3 >= total_weight * 2 {
+            self.finalized_height = attestation.get_block_height();
+        }
+
+        true
+    }
+
+    fn remove_expired_transactions_mempool(&mut self, timestamp: u64) {
+        // the next block is at least at the next block height and after the given block timestamp
+        let block_height = self.get_block_height() + 1;
+
+        for transaction_vec in self.mempool.values_mut() {
+            transaction_vec.retain(|transaction| !transaction.is_expired(block_height, timestamp));
+        }
+
+        // remove the entries of senders with no more transactions in the mempool
+        self.mempool.retain(|_, transaction_vec| !transaction_vec.is_empty());
+    }
+
     pub fn remove_transaction_mempool(&mut self, transaction: &Transaction) {
-        match self.mempool.get_mut(&transaction.sender) {
+        let sender_address = match self.get_sender_address(transaction) {
+            Some(sender_address) => sender_address,
+            None => return
+        };
+
+        match self.mempool.get_mut(&sender_address) {
             Some(transaction_vec) => {
                 // remove the matched transaction
                 transaction_vec.retain(|tx| {
@@ -129,32 +363,35 @@ impl Blockchain {
 
                 // if the transaction sender has no more transactions in the mempool then remove their entry from the mempool hashmap
                 if transaction_vec.len() == 0 {
-                    self.mempool.remove(&transaction.sender);
+                    self.mempool.remove(&sender_address);
                 }
             },
             None => ()
         }
     }
 
-    fn update_chain(&mut self, block: &Block) -> bool {
+    fn update_chain(&mut self, block: &Block, proposer: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>) -> bool {
         // this function assumes that the blocks given to it are valid with the current chain state
         // this function should only ever be called after the verification engine has verified all transactions within the given block with the current chain state
 
         // iterate through all transactions of the given block
         for transaction in block.get_transactions() {
-            if !self.update_chain_transaction(transaction, block) {
+            if !self.update_chain_transaction(transaction, block, proposer) {
                 return false
             }
         }
 
+        // the next block starts without any validator enable transactions
+        self.validator_enables = 0;
+
         true
     }
 
-    pub fn update_chain_transaction(&mut self, transaction: &Transaction, block: &Block) -> bool {
+    pub fn update_chain_transaction(&mut self, transaction: &Transaction, block: &Block, proposer: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>) -> bool {
         // get the validator address for this block
         let validator_address: [u8; BLOCK_ADDRESS_SIZE] = match block.get_transactions().get(0) {
             Some(transaction) => {
-                if verification_engine::is_coinbase(transaction, Some(block), self.get_block_height()) {
+                if verification_engine::is_coinbase(transaction, Some(block), self) {
                     transaction.recipient
                 } else {
                     // if there is no coinbase transaction then the validator will lose all rewards for this block
@@ -166,225 +403,534 @@ impl Blockchain {
         };
 
         // transaction is a validator enable transaction
-        if verification_engine::is_validator_enable(&transaction, self) {
-            // increase the block validator's balance by the transaction fee
-            match self.accounts.get_mut(&validator_address) {
-                // increase the block validator's balance by the transaction fee
-                Some(account) => account.increase_balance(transaction.fee),
-                None => {
-                    // create a new account for this newly discovered address
-                    self.accounts.insert(validator_address, Account::new(validator_address));
-                    // increase the block validator's balance by the transaction fee
-                    self.accounts.get_mut(&validator_address).unwrap().increase_balance(transaction.fee);
-                }
+        if verification_engine::is_validator_enable(transaction, Some(block), self) {
+            let genesis = verification_engine::is_genesis_block(Some(block));
+
+            // validators in the genesis block can be chosen right away, every other validator is on probation starting with the next block
+            let activation_height = if genesis { 0 } else { self.get_block_height() + 1 + *VALIDATOR_PROBATION_PERIOD };
+
+            // during the bootstrapping phase the minimum validator enable fee is burned if it is more than the base fee
+            // burn it and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee.max(self.get_minimum_validator_enable_fee()));
+
+            // add the account to the list of validators
+            self.add_validator(transaction.sender, activation_height);
+
+            // count the validator enable transaction towards the block's MAX_VALIDATOR_ENABLES_PER_BLOCK
+            if !genesis {
+                self.validator_enables += 1;
+            }
+
+            // get the address of the account, its key may have been rotated
+            let account_address = match self.get_address(&transaction.sender) {
+                Some(account_address) => account_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
             };
 
-            // get the account public key
-            let account_pub_key = match PublicKey::from_sec1_bytes(&transaction.sender) {
-                Ok(account_pub_key) => account_pub_key,
+            // increment the account nonce, set the account as a validator, and set the stake as the transaction amount
+            let account = self.get_or_create_account(account_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.amount + transaction.fee);
+            account.set_stake(transaction.amount);
+            account.set_validator(true);
+
+            // increase the balance of the VALIDATOR_ENABLE_RECIPIENT
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).increase_balance(transaction.amount);
+        }
+        // transaction is a validator revoke trasnaction
+        else if verification_engine::is_validator_revoke(&transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // return all stake delegated to the validator through the unbonding queue, then remove the account from the list of validators
+            self.undelegate_all(transaction.sender, true);
+            self.remove_validator(transaction.sender);
+
+            // get the address of the account, its key may have been rotated
+            let account_address = match self.get_address(&transaction.sender) {
+                Some(account_address) => account_address,
                 // This should NEVER happen since this block must have been validated by the verification_engine first
-                Err(_) => return false
+                None => return false
             };
 
-            // add the account to the list of validators
-            self.validators.push(ValidatorAccount::new(transaction.sender));
+            // increment the nonce, set the account as not a validator, lock the transaction amount in the unbonding queue, and set their current stake back to 0
+            let account = self.get_or_create_account(account_address);
+            account.increase_nonce();
+            account.set_stake(0);
+            account.decrease_balance(transaction.fee);
+            account.increase_unbonding(transaction.amount);
+            account.set_validator(false);
 
-            // get the address for the account public key
-            let account_address = Wallet::generate_address(&account_pub_key, true);
+            // the revoked stake is released to the account balance once the unbonding period has passed, the block being applied is at block_height + 1
+            self.unbonding_queue.push(UnbondingEntry::new(account_address, transaction.amount, self.get_block_height() + 1 + *UNBONDING_PERIOD));
 
-            // increment the account nonce, set the account as a validator, and set the stake as the transaction amount
-            match self.accounts.get_mut(&account_address) {
-                Some(account) => {
-                    account.increase_nonce();
-                    account.decrease_balance(transaction.amount + transaction.fee);
-                    account.set_stake(transaction.amount);
-                    account.set_validator(true);
-                },
-                None => {
-                    // create new account for this newly discovered address
-                    self.accounts.insert(account_address, Account::new(account_address));
-
-                    let account = self.accounts.get_mut(&account_address).unwrap();
-                    account.increase_nonce();
-                    account.decrease_balance(transaction.amount + transaction.fee);
-                    account.set_stake(transaction.amount);
-                    account.set_validator(true);
-                }
+            // decrease the balance of the VALIDATOR_ENABLE_RECIPIENT
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).decrease_balance(transaction.amount);
+        }
+        // transaction is a validator stake increase transaction
+        else if verification_engine::is_validator_stake_increase(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the address of the account, its key may have been rotated
+            let account_address = match self.get_address(&transaction.sender) {
+                Some(account_address) => account_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
             };
 
+            // increment the account nonce and move the transaction amount from the account balance to its stake
+            let account = self.get_or_create_account(account_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.amount + transaction.fee);
+            account.set_stake(account.get_stake() + transaction.amount);
+
             // increase the balance of the VALIDATOR_ENABLE_RECIPIENT
-            match self.accounts.get_mut(VALIDATOR_ENABLE_RECIPIENT) {
-                Some(account) => {
-                    // increase the VALIDATOR_ENABLE_RECIPIENT's balance
-                    account.increase_balance(transaction.amount);
-                },
-                None => {
-                    // create new account for this newly discovered address
-                    self.accounts.insert(*VALIDATOR_ENABLE_RECIPIENT, Account::new(*VALIDATOR_ENABLE_RECIPIENT));
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).increase_balance(transaction.amount);
+        }
+        // transaction is a validator stake withdraw transaction
+        else if verification_engine::is_validator_stake_withdraw(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the address of the account, its key may have been rotated
+            let account_address = match self.get_address(&transaction.sender) {
+                Some(account_address) => account_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
 
-                    // increase the VALIDATOR_ENABLE_RECIPIENT's balance
-                    let account = self.accounts.get_mut(VALIDATOR_ENABLE_RECIPIENT).unwrap();
-                    account.increase_balance(transaction.amount);
-                }
+            // increment the account nonce, reduce the account's stake and lock the withdrawn amount in the unbonding queue, the account remains a validator
+            let account = self.get_or_create_account(account_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.fee);
+            account.set_stake(account.get_stake() - transaction.amount);
+            account.increase_unbonding(transaction.amount);
+
+            // the withdrawn stake is released to the account balance once the unbonding period has passed, the block being applied is at block_height + 1
+            self.unbonding_queue.push(UnbondingEntry::new(account_address, transaction.amount, self.get_block_height() + 1 + *UNBONDING_PERIOD));
+
+            // decrease the balance of the VALIDATOR_ENABLE_RECIPIENT
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).decrease_balance(transaction.amount);
+        }
+        // transaction is a delegation to a validator
+        else if verification_engine::is_delegate(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the address of the account, its key may have been rotated
+            let account_address = match self.get_address(&transaction.sender) {
+                Some(account_address) => account_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
             };
+
+            // increment the account nonce and move the transaction amount from the account balance to its delegated total
+            let account = self.get_or_create_account(account_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.amount + transaction.fee);
+            account.increase_delegated(transaction.amount);
+
+            // add the delegation to the validator
+            match self.get_validator_by_address_mut(&transaction.recipient) {
+                Some(validator) => validator.add_delegation(account_address, transaction.amount),
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            // delegated funds are held by the VALIDATOR_ENABLE_RECIPIENT along with the validators' own stake
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).increase_balance(transaction.amount);
         }
-        // transaction is a validator revoke trasnaction
-        else if verification_engine::is_validator_revoke(&transaction, self) {
-            // increase the block validator's balance by the transaction fee
-            match self.accounts.get_mut(&validator_address) {
-                // increase the block validator's balance by the transaction fee
-                Some(account) => account.increase_balance(transaction.fee),
-                None => {
-                    // create a new account for this newly discovered address
-                    self.accounts.insert(validator_address, Account::new(validator_address));
-                    // increase the block validator's balance by the transaction fee
-                    self.accounts.get_mut(&validator_address).unwrap().increase_balance(transaction.fee);
-                }
+        // transaction is an undelegation from a validator
+        else if verification_engine::is_undelegate(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the address of the account, its key may have been rotated
+            let account_address = match self.get_address(&transaction.sender) {
+                Some(account_address) => account_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
             };
 
-            // get the account public key
-            let account_pub_key = match PublicKey::from_sec1_bytes(&transaction.sender) {
-                Ok(account_pub_key) => account_pub_key,
+            // remove the amount from the validator's delegations
+            match self.get_validator_by_address_mut(&transaction.recipient) {
+                Some(validator) => validator.remove_delegation(account_address, transaction.amount),
                 // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            // increment the account nonce and lock the undelegated amount in the unbonding queue
+            let account = self.get_or_create_account(account_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.fee);
+            account.decrease_delegated(transaction.amount);
+            account.increase_unbonding(transaction.amount);
+
+            // the undelegated funds are released to the account balance once the unbonding period has passed, the block being applied is at block_height + 1
+            self.unbonding_queue.push(UnbondingEntry::new(account_address, transaction.amount, self.get_block_height() + 1 + *UNBONDING_PERIOD));
+
+            // decrease the balance of the VALIDATOR_ENABLE_RECIPIENT
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).decrease_balance(transaction.amount);
+        }
+        // transaction sets a validator's commission
+        else if verification_engine::is_validator_commission(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the address of the account, its key may have been rotated
+            let account_address = match self.get_address(&transaction.sender) {
+                Some(account_address) => account_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            // increment the account nonce
+            let account = self.get_or_create_account(account_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.fee);
+
+            // set the validator's commission
+            match (transaction.data, self.get_validator_by_address_mut(&account_address)) {
+                (TxData::ValidatorCommission(commission), Some(validator)) => validator.set_commission(commission),
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                _ => return false
+            };
+        }
+        // transaction locks funds in a hash time-locked transfer
+        else if verification_engine::is_htlc_lock(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the sender address, the sender can be a single key or a multisig account
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            // the transfer is identified by the id of the transaction that locked it
+            let lock_id: [u8; 32] = match transaction.serialize_hash_txid().try_into() {
+                Ok(lock_id) => lock_id,
                 Err(_) => return false
             };
 
-            // remove the account from the list of validators
-            for i in 0..self.validators.len() {
-                if self.validators[i].get_public_key() == transaction.sender {
-                    self.validators.remove(i);
-                    break;
-                }
-            }
+            // add the transfer to the registry
+            match transaction.data {
+                TxData::HtlcLock { hash_lock, refund_height } => self.htlcs.insert(lock_id, Htlc::new(sender_address, transaction.recipient, transaction.amount, hash_lock, refund_height)),
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                _ => return false
+            };
 
-            // get the address for the account public key
-            let account_address = Wallet::generate_address(&account_pub_key, true);
+            // decrease the sender balance by the locked amount + fees, and increase the nonce
+            let account = self.get_or_create_account(sender_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.amount + transaction.fee);
 
-            // increment the nonce, set the account as not a validator, increase their balance by the transaction amount, and set their current stake back to 0
-            match self.accounts.get_mut(&account_address) {
-                Some(account) => {
-                    account.increase_nonce();
-                    account.set_stake(0);
-                    account.decrease_balance(transaction.fee);
-                    account.increase_balance(transaction.amount);
-                    account.set_validator(false);
+            // locked funds are held by the HTLC_RECIPIENT until they are claimed or refunded
+            self.get_or_create_account(*HTLC_RECIPIENT).increase_balance(transaction.amount);
+        }
+        // transaction claims or refunds a hash time-locked transfer
+        else if verification_engine::is_htlc_claim(transaction, self) || verification_engine::is_htlc_refund(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the sender address, the sender can be a single key or a multisig account
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            // remove the transfer from the registry, it can only be claimed or refunded once
+            let htlc = match transaction.data {
+                TxData::HtlcClaim { lock_id, .. } | TxData::HtlcRefund { lock_id } => match self.htlcs.remove(&lock_id) {
+                    Some(htlc) => htlc,
+                    // This should NEVER happen since this block must have been validated by the verification_engine first
+                    None => return false
                 },
-                None => {
-                    // create a new account for this newly discovered address
-                    self.accounts.insert(account_address, Account::new(account_address));
-
-                    let account = self.accounts.get_mut(&account_address).unwrap();
-                    account.increase_nonce();
-                    account.set_stake(0);
-                    account.decrease_balance(transaction.fee);
-                    account.increase_balance(transaction.amount);
-                    account.set_validator(false);
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                _ => return false
+            };
+
+            // the claimer or the refunded sender receives the locked amount, the fee can be paid out of it, and increases their nonce
+            let account = self.get_or_create_account(sender_address);
+            account.increase_nonce();
+            account.increase_balance(htlc.get_amount());
+            account.decrease_balance(transaction.fee);
+
+            // decrease the balance of the HTLC_RECIPIENT
+            self.get_or_create_account(*HTLC_RECIPIENT).decrease_balance(htlc.get_amount());
+        }
+        // transaction opens a payment channel
+        else if verification_engine::is_channel_open(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the sender address, payment channels can't be opened by multisig accounts
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            // the channel is identified by the id of the transaction that opened it
+            let channel_id: [u8; 32] = match transaction.serialize_hash_txid().try_into() {
+                Ok(channel_id) => channel_id,
+                Err(_) => return false
+            };
+
+            // add the channel to the registry, the payer signs channel updates with the key that opened it
+            self.payment_channels.insert(channel_id, PaymentChannel::new(transaction.sender, sender_address, transaction.recipient, transaction.amount));
+
+            // decrease the sender balance by the deposit + fees, and increase the nonce
+            let account = self.get_or_create_account(sender_address);
+            account.increase_nonce();
+            account.decrease_balance(transaction.amount + transaction.fee);
+
+            // deposited funds are held by the CHANNEL_RECIPIENT until the channel is settled
+            self.get_or_create_account(*CHANNEL_RECIPIENT).increase_balance(transaction.amount);
+        }
+        // transaction closes a payment channel
+        else if verification_engine::is_channel_close(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the sender address, the sender is the payer or the payee of the channel
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            let channel_update = match transaction.data {
+                TxData::ChannelClose(channel_update) => channel_update,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                _ => return false
+            };
+
+            let channel = match self.payment_channels.get_mut(&channel_update.get_channel_id()) {
+                Some(channel) => channel,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            if sender_address == channel.get_payee() {
+                // the payee has no reason to close with anything but its latest update, so the channel is settled right away
+                let channel = channel.clone();
+                self.payment_channels.remove(&channel_update.get_channel_id());
+                self.settle_payment_channel(&channel, channel_update.get_paid());
+
+                // increment the payee's nonce, the fee can be paid out of the settled funds
+                let account = self.get_or_create_account(sender_address);
+                account.increase_nonce();
+                account.decrease_balance(transaction.fee);
+            } else {
+                // the payer may close with a stale update, so the payee gets the dispute period to close it with a later one
+                channel.close(channel_update.get_paid(), self.block_height + 1 + *CHANNEL_DISPUTE_PERIOD);
+
+                // increment the payer's nonce
+                let account = self.get_or_create_account(sender_address);
+                account.increase_nonce();
+                account.decrease_balance(transaction.fee);
+            }
+        }
+        // transaction rotates the public key of the sending account
+        else if verification_engine::is_key_rotation(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the sender address, multisig accounts can't rotate their keys
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            let new_public_key = match transaction.data {
+                TxData::KeyRotation(new_public_key) => new_public_key,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                _ => return false
+            };
+
+            // the old key no longer controls the account, if it was a rotated key it controls the address generated from it again
+            if self.rotated_keys.remove(&transaction.sender).is_some() {
+                if let Some(old_key_address) = Self::get_generated_address(&transaction.sender) {
+                    self.rotated_key_addresses.remove(&old_key_address);
                 }
+            }
+
+            // rotating back to the key the address was generated from doesn't need a binding
+            let rotated_public_key = if self.get_address(&new_public_key) == Some(sender_address) {
+                None
+            } else {
+                self.rotated_keys.insert(new_public_key, sender_address);
+                if let Some(new_key_address) = Self::get_generated_address(&new_public_key) {
+                    self.rotated_key_addresses.insert(new_key_address);
+                }
+                Some(new_public_key)
             };
 
-            // decrease the balance of the VALIDATOR_ENABLE_RECIPIENT
-            match self.accounts.get_mut(VALIDATOR_ENABLE_RECIPIENT) {
-                Some(account) => {
-                    // decrease the VALIDATOR_ENABLE_RECIPIENT's balance
-                    account.decrease_balance(transaction.amount);
+            // decrease the sender balance by the fee, increase the nonce and bind the account to its new key
+            let account = self.get_or_create_account(sender_address);
+            account.decrease_balance(transaction.fee);
+            account.increase_nonce();
+            account.set_public_key(rotated_public_key);
+
+            // a validator keeps its stake, delegations and place in the current epoch under its new key
+            if let Some(validator) = self.validators.iter_mut().find(|validator| validator.get_public_key() == transaction.sender) {
+                validator.set_public_key(new_public_key);
+            }
+
+            if let Some(epoch) = self.epochs.last_mut() {
+                epoch.replace_public_key(&transaction.sender, new_public_key);
+            }
+        }
+        // transaction registers or renews a name
+        else if verification_engine::is_name_registration(transaction, self) || verification_engine::is_name_renewal(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the sender address, the sender can be a single key or a multisig account
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
+            };
+
+            // the block the transaction is added to is at block_height + 1
+            let block_height = self.block_height + 1;
+            match transaction.data {
+                // a registration replaces any expired registration of the name
+                TxData::NameRegister(name) => {
+                    self.names.insert(name, NameRecord::new(sender_address, block_height + *NAME_REGISTRATION_PERIOD));
                 },
-                None => {
-                    // create new account for this newly discovered address
-                    self.accounts.insert(*VALIDATOR_ENABLE_RECIPIENT, Account::new(*VALIDATOR_ENABLE_RECIPIENT));
+                // a renewal extends the registration from its expiry height, or from this block if it has already expired
+                TxData::NameRenew(name) => match self.names.get_mut(&name) {
+                    Some(name_record) => name_record.set_expiry_height(name_record.get_expiry_height().max(block_height) + *NAME_REGISTRATION_PERIOD),
+                    // This should NEVER happen since this block must have been validated by the verification_engine first
+                    None => return false
+                },
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                _ => return false
+            }
 
-                    // decrease the VALIDATOR_ENABLE_RECIPIENT's balance
-                    let account = self.accounts.get_mut(VALIDATOR_ENABLE_RECIPIENT).unwrap();
-                    account.decrease_balance(transaction.amount);
-                }
+            // decrease the sender balance by the registration fee + fees, and increase the nonce
+            let account = self.get_or_create_account(sender_address);
+            account.decrease_balance(transaction.amount + transaction.fee);
+            account.increase_nonce();
+
+            // the registration fee is burned
+            self.get_or_create_account(*BURN_RECIPIENT).increase_balance(transaction.amount);
+        }
+        // transaction transfers a name
+        else if verification_engine::is_name_transfer(transaction, self) {
+            // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+            self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
+
+            // get the sender address, the sender can be a single key or a multisig account
+            let sender_address = match self.get_sender_address(transaction) {
+                Some(sender_address) => sender_address,
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                None => return false
             };
+
+            // the name now resolves to the recipient
+            match transaction.data {
+                TxData::NameTransfer(name) => match self.names.get_mut(&name) {
+                    Some(name_record) => name_record.set_owner(transaction.recipient),
+                    // This should NEVER happen since this block must have been validated by the verification_engine first
+                    None => return false
+                },
+                // This should NEVER happen since this block must have been validated by the verification_engine first
+                _ => return false
+            }
+
+            // decrease the sender balance by the fee, and increase the nonce
+            let account = self.get_or_create_account(sender_address);
+            account.decrease_balance(transaction.fee);
+            account.increase_nonce();
         } else {
             // transaction is a coinbase transaction
-            if verification_engine::is_coinbase(&transaction, Some(block), self.get_block_height()) {
-                match self.accounts.get_mut(&transaction.recipient) {
-                    // increase the balance by the coinbase amount
-                    Some(account) => account.increase_balance(transaction.amount),
-                    None => {
-                        // create a new account for this newly discovered address
-                        self.accounts.insert(transaction.recipient, Account::new(transaction.recipient));
-                        // update the balance of the recipient account
-                        self.accounts.get_mut(&transaction.recipient).unwrap().increase_balance(transaction.amount);
-                    }
+            if verification_engine::is_coinbase(transaction, Some(block), self) {
+                // any amount over the block subsidy is loose change recycled into the block reward, take it out of the loose change pool
+                let recycled_change = transaction.amount.saturating_sub(verification_engine::get_block_subsidy(self.get_block_height() + 1));
+                self.get_or_create_account(*LOOSE_CHANGE_RECIPIENT).decrease_balance(recycled_change);
+
+                // only the block subsidy is newly issued
+                self.issued += transaction.amount - recycled_change;
+
+                // pay the coinbase amount to the recipient and the block validator's delegators
+                self.credit_block_reward(transaction.recipient, proposer, transaction.amount);
+            }
+            // transaction is a batch transaction
+            else if verification_engine::is_batch(transaction) {
+                // burn the base fee for each output and pay the rest of the transaction fee to the block validator and its delegators
+                self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee * transaction.get_weight());
+
+                // get the sender address, the sender can be a single key or a multisig account
+                let sender_address = match self.get_sender_address(transaction) {
+                    Some(sender_address) => sender_address,
+                    // This should NEVER happen since this block must have been validated by the verification_engine first
+                    None => return false
                 };
+
+                // decrease the sender balance by the total of the outputs + fees, and increase the nonce
+                let account = self.get_or_create_account(sender_address);
+                account.decrease_balance(transaction.amount + transaction.fee);
+                account.increase_nonce();
+
+                // increase the balance of every output's recipient, the verification engine confirmed the outputs add up to the transaction amount
+                for (recipient, amount) in transaction.outputs.iter() {
+                    self.get_or_create_account(*recipient).increase_balance(*amount);
+                }
             }
             // transaction is a typical A -> B transaction
             else {
-                // increase the block validator's balance by the transaction fee
-                match self.accounts.get_mut(&validator_address) {
-                    // increase the block validator's balance by the transaction fee
-                    Some(account) => account.increase_balance(transaction.fee),
-                    None => {
-                        // create a new account for this newly discovered address
-                        self.accounts.insert(validator_address, Account::new(validator_address));
-                        // increase the block validator's balance by the transaction fee
-                        self.accounts.get_mut(&validator_address).unwrap().increase_balance(transaction.fee);
-                    }
-                };
+                // burn the base fee and pay the rest of the transaction fee to the block validator and its delegators
+                self.pay_transaction_fee(validator_address, proposer, transaction.fee, self.base_fee);
                 
                 // increase the receipients balance by the transaction amount
-                match self.accounts.get_mut(&transaction.recipient) {
-                    // increase the receipients balance by the transaction amount
-                    Some(account) => account.increase_balance(transaction.amount),
-                    None => {
-                        // create a new account for this newly discovered address
-                        self.accounts.insert(transaction.recipient, Account::new(transaction.recipient));
-                        // update the balance of the recipient account
-                        self.accounts.get_mut(&transaction.recipient).unwrap().increase_balance(transaction.amount);
-                    }
-                };
+                self.get_or_create_account(transaction.recipient).increase_balance(transaction.amount);
 
                 // decrease the sender balance by the transaction amount + fees, and increase the nonce
-                // get the account public key
-                let validator_pub_key = match PublicKey::from_sec1_bytes(&transaction.sender) {
-                    Ok(validator_pub_key) => validator_pub_key,
+                // get the sender address, the sender can be a single key or a multisig account
+                let sender_address = match self.get_sender_address(transaction) {
+                    Some(sender_address) => sender_address,
                     // This should NEVER happen since this block must have been validated by the verification_engine first
-                    Err(_) => return false
+                    None => return false
                 };
 
                 // update the balance of the sender account
-                match self.accounts.get_mut(&Wallet::generate_address(&validator_pub_key, true)) {
-                    Some(account) => {
-                        account.decrease_balance(transaction.amount + transaction.fee);
-                        account.increase_nonce();
-                    },
-                    None => {
-                        let new_address = Wallet::generate_address(&validator_pub_key, true);
-                        // create a new account for this newly discovered address
-                        self.accounts.insert(new_address, Account::new(new_address));
-                        // update the balance of the sender account
-                        self.accounts.get_mut(&new_address).unwrap().decrease_balance(transaction.amount + transaction.fee);
-                        // update the nonce of the sender account
-                        self.accounts.get_mut(&new_address).unwrap().increase_nonce();
-                    }
-                };
+                let account = self.get_or_create_account(sender_address);
+                account.decrease_balance(transaction.amount + transaction.fee);
+                account.increase_nonce();
             }
         }
 
         true
     }
 
-    pub fn calculate_proposer(&self, validator_list: Vec<ValidatorAccount>, previous_validator_pub_key: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>) -> Option<([u8; COMPRESSED_PUBLIC_KEY_SIZE], usize)> {
+    pub fn calculate_proposer(&self, validator_list: Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)>, previous_validator_pub_key: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>) -> Option<([u8; COMPRESSED_PUBLIC_KEY_SIZE], usize)> {
         let mut proposer_hash = match previous_validator_pub_key {
             Some(previous_validator_pub_key) => {
                 // Note: the previous_validator_pub_key is NOT the previous blocks validator's public key, it is the previous validator that would've been chosen for the CURRENT block
                 // this is used in the scenario where a validator didn't propose a block and they were the chosen validator. The previous_validator_pub_key is used as a "seed" for choosing a new validator
                 
-                // concatenate the previous blocks hash and the previously chosen validator for this block
+                // concatenate the randomness beacon and the previously chosen validator for this block
                 let mut concatenated_prev_hash_previous_validator_pub_key: Vec<u8> = vec![];
-                concatenated_prev_hash_previous_validator_pub_key.append(&mut self.get_last_block().serialize_hash_block_header());
+                concatenated_prev_hash_previous_validator_pub_key.append(&mut self.randomness_beacon.to_vec());
                 concatenated_prev_hash_previous_validator_pub_key.append(&mut previous_validator_pub_key.clone().to_vec());
 
-                // sha256(prev_hash + previous_validator_pub_key)
+                // sha256(randomness_beacon + previous_validator_pub_key)
                 let mut sha256_hasher: Sha256 = Sha256::new();
                 sha256_hasher.update(concatenated_prev_hash_previous_validator_pub_key);
                 sha256_hasher.finalize().to_vec()
             },
-            // if this is the first validator (there is no previously attetmpted validator) then just use the previous block hash as the "seed"
-            None => self.get_last_block().serialize_hash_block_header()
+            // if this is the first validator (there is no previously attetmpted validator) then just use the randomness beacon as the "seed"
+            // the beacon can't be ground by the previous proposer like the previous block hash could, since their vrf output is unique for the previous beacon
+            None => self.randomness_beacon.to_vec()
         };
 
         // get the bottom 64 bits of the hash
@@ -396,24 +942,9 @@ impl Blockchain {
         // get the total stake the validator_list has staked
         let mut total_stake = 0;
 
-        for i in 0..validator_list.len() {
-            let validator_pub_key = match PublicKey::from_sec1_bytes(&validator_list[i].get_public_key()) {
-                Ok(validator_pub_key) => validator_pub_key,
-                // should never get here
-                Err(_) => continue
-            };
-
-            // get the address for the account public key
-            let account_address = Wallet::generate_address(&validator_pub_key, true);
-
-            let validator_account = match self.get_account(&account_address) {
-                Some(validator_account) => validator_account,
-                // Should never get here
-                None => continue
-            };
-
+        for (_, validator_weight) in validator_list.iter() {
             // accumulate the total stake variable
-            total_stake += validator_account.get_stake();
+            total_stake += validator_weight;
         }
 
         // if the blockchain is out of the bootstrapping phase mod the bottom 64 bits integer with the total amount the validator_list has staked
@@ -424,28 +955,12 @@ impl Blockchain {
             let mut total_staked_accumulation = 0;
 
             for i in 0..validator_list.len() {
-                let validator_pub_key = match PublicKey::from_sec1_bytes(&validator_list[i].get_public_key()) {
-                    Ok(validator_pub_key) => validator_pub_key,
-                    // should never get here
-                    Err(_) => continue
-                };
-
-                // get the address for the account public key
-                let account_address = Wallet::generate_address(&validator_pub_key, true);
-
-                // get the validator account
-                let validator_account = match self.get_account(&account_address) {
-                    Some(validator_account) => validator_account,
-                    // Should never get here
-                    None => continue
-                };
-
                 // accumulate the total 
-                total_staked_accumulation += validator_account.get_stake();
+                total_staked_accumulation += validator_list[i].1;
 
                 // when the winning number has been reached return the winning validator
                 if total_staked_accumulation >= winning_number {
-                    return Some((validator_list[i].get_public_key(), i))
+                    return Some((validator_list[i].0, i))
                 }
             }
         } else {
@@ -454,7 +969,7 @@ impl Blockchain {
             // this allows for an attack vector on the network, since you can create unlimited accounts for free (during the bootstrapping phase)
             if validator_list.len() != 0 {
                 let winning_number = bottom_64_as_integer % TryInto::<u64>::try_into(validator_list.len()).unwrap();
-                return Some((validator_list[winning_number as usize].get_public_key(), winning_number as usize))
+                return Some((validator_list[winning_number as usize].0, winning_number as usize))
             }
         }
 
@@ -462,19 +977,329 @@ impl Blockchain {
         None
     }
 
+    pub fn get_proposer_schedule(&self) -> Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)> {
+        // the ordered list of validators that may propose the next block, each with the earliest timestamp their block is valid at
+        // the first validator is the chosen proposer, each following validator may propose if all of the validators before them didn't
+        let mut schedule = vec![];
+
+        // the pub key of the previously chosen validator, used as a "seed" for choosing the next validator
+        let mut previous_validator_pub_key: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> = None;
+
+        // validators of the current epoch that haven't been chosen yet
+        let mut validator_list = self.get_current_epoch_validators();
+
+        // timestamp of the last block, the next block can't be proposed until 5 minutes after it
+        let previous_block_timestamp = self.get_last_block().get_timesamp();
+
+        while let Some((proposer_pub_key, proposer_pub_key_index)) = self.calculate_proposer(validator_list.clone(), previous_validator_pub_key) {
+            // 5 minutes after the previous block + 2 minutes for every validator that was chosen before this one
+            let earliest_timestamp = previous_block_timestamp + 300 + TryInto::<u64>::try_into(schedule.len()).unwrap() * 120;
+            schedule.push((proposer_pub_key, earliest_timestamp));
+
+            // remove the chosen validator from the possible validators list and use them as the seed for the next choice
+            previous_validator_pub_key = Some(proposer_pub_key);
+            validator_list.remove(proposer_pub_key_index);
+        }
+
+        schedule
+    }
+
+    pub fn get_proposer_probability(&self, public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE]) -> f64 {
+        // the probability of the validator being the chosen proposer of a block, mirrors the choice made in calculate_proposer
+        let validator_list = self.get_current_epoch_validators();
+
+        let validator_weight = match validator_list.iter().find(|(validator_pub_key, _)| validator_pub_key == public_key) {
+            Some((_, validator_weight)) => *validator_weight,
+            None => return 0.0
+        };
+
+        let total_stake: u64 = validator_list.iter().map(|(_, validator_weight)| validator_weight).sum();
+
+        if self.get_block_height() > *BOOTSTRAPPING_PHASE_BLOCK_HEIGHT && total_stake != 0 {
+            validator_weight as f64 / total_stake as f64
+        } else {
+            // during the bootstrapping phase every validator is equally likely to be chosen
+            1.0 / validator_list.len() as f64
+        }
+    }
+
+    pub fn get_attested_weight(&self, block_height: u64) -> (u64, u64) {
+        // the stake of the current epoch's validators that have attested to the checkpoint and the stake of all of the current epoch's validators
+        // attestations are always weighted by stake, even during the bootstrapping phase, so creating free validator accounts can't finalize a checkpoint
+        let checkpoint_attestations = self.attestations.get(&block_height).cloned().unwrap_or_default();
+
+        let mut attested_weight = 0;
+        let mut total_weight = 0;
+
+        for (validator_pub_key, weight) in self.get_current_epoch_validators() {
+            if checkpoint_attestations.iter().any(|attestation| attestation.get_public_key() == validator_pub_key) {
+                attested_weight += weight;
+            }
+            total_weight += weight;
+        }
+
+        (attested_weight, total_weight)
+    }
+
+    pub fn get_deployment_state(&self, index: usize) -> Option<(DeploymentState, u64)> {
+        self.deployment_states.get(index).copied()
+    }
+
+    pub fn get_deployment_signals(&self, index: usize) -> u64 {
+        // number of blocks in the current window (since the last window ended) that signal for the deployment
+        if self.block_height == 0 {
+            return 0
+        }
+
+        let window_start = self.block_height - (self.block_height - 1) % *DEPLOYMENT_WINDOW;
+
+        match DEPLOYMENTS.get(index) {
+            Some(deployment) => self.blocks[window_start as usize..=self.block_height as usize].iter().filter(|block| deployment.is_signaled(block.get_version())).count() as u64,
+            None => 0
+        }
+    }
+
+    pub fn is_deployment_active(&self, name: &str) -> bool {
+        // every deployment's rules are enforced on their own, based only on the state of that deployment
+        DEPLOYMENTS.iter().zip(self.deployment_states.iter())
+            .any(|(deployment, (state, _))| deployment.get_name() == name && *state == DeploymentState::Active)
+    }
+
+    pub fn get_next_block_version(&self) -> u32 {
+        // validators signal for every deployment that has started, this node can enforce all of their rules
+        let signals = DEPLOYMENTS.iter().zip(self.deployment_states.iter())
+            .filter(|(_, (state, _))| *state == DeploymentState::Started)
+            .fold(0, |signals, (deployment, _)| signals | deployment.get_signal());
+
+        // validators propose blocks in the format of the newest active signature features
+        let format_version = if self.is_deployment_active("schnorr") {
+            *SCHNORR_BLOCK_VERSION
+        } else if self.is_deployment_active("chain_id") {
+            *CHAIN_ID_BLOCK_VERSION
+        } else {
+            *BLOCK_VERSION
+        };
+
+        format_version | signals
+    }
+
+    pub fn get_max_block_version(&self) -> u32 {
+        // the newest block format version this node understands, a format version only selects how a block is serialized and signed
+        DEPLOYMENTS.iter().fold(*BLOCK_VERSION, |version, deployment| version.max(deployment.get_block_version()))
+    }
+
+    pub fn get_max_transaction_version(&self) -> u8 {
+        // the newest transaction version this node understands, a version only selects how a transaction is serialized and signed
+        DEPLOYMENTS.iter().fold(*DATA_TRANSACTION_VERSION, |version, deployment| version.max(deployment.get_transaction_version()))
+    }
+
+    pub fn get_tx_format(&self) -> TxFormat {
+        // the signature features new transactions are created with
+        TxFormat::new(self.get_signed_chain_id(), self.is_deployment_active("chain_id"), self.is_deployment_active("schnorr"))
+    }
+
+    pub fn get_loose_change_reward(&self) -> u64 {
+        self.loose_change_reward
+    }
+
+    pub fn get_minimum_replacement_fee(&self, transaction: &Transaction) -> u64 {
+        // the fee a transaction must pay to replace the given pending transaction, paying the base fee again keeps replacements from being relayed for free
+        transaction.fee + (transaction.fee / *REPLACEMENT_FEE_INCREASE_DENOMINATOR).max(self.base_fee * transaction.get_weight())
+    }
+
+    pub fn get_base_fee(&self) -> u64 {
+        self.base_fee
+    }
+
+    pub fn get_total_burned(&self) -> u64 {
+        match self.accounts.get(BURN_RECIPIENT) {
+            Some(account) => account.get_balance(),
+            None => 0
+        }
+    }
+
+    pub fn get_minimum_validator_enable_fee(&self) -> u64 {
+        // there is only a minimum validator enable fee during the bootstrapping phase, after it validators must stake the MINIMUM_STAKING_AMOUNT instead
+        if self.get_block_height() < *BOOTSTRAPPING_PHASE_BLOCK_HEIGHT {
+            *MINIMUM_VALIDATOR_ENABLE_FEE
+        } else {
+            0
+        }
+    }
+
+    pub fn get_validator_weight(&self, validator: &ValidatorAccount) -> u64 {
+        // a validator's weight is its own stake plus the stake delegated to it
+        let validator_address = match self.get_address(&validator.get_public_key()) {
+            Some(validator_address) => validator_address,
+            // should never get here
+            None => return 0
+        };
+
+        // get the validator account
+        let stake = match self.get_account(&validator_address) {
+            Some(validator_account) => validator_account.get_stake(),
+            // Should never get here
+            None => 0
+        };
+
+        stake + validator.get_delegated_stake()
+    }
+
+    fn get_or_create_account(&mut self, address: [u8; BLOCK_ADDRESS_SIZE]) -> &mut Account {
+        // get the account for the given address, creating a new account if this is a newly discovered address
+        self.accounts.entry(address).or_insert_with(|| Account::new(address))
+    }
+
+    fn add_validator(&mut self, public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE], activation_height: u64) {
+        // only add the validator if they aren't already in the list of validators, there must be exactly one entry per public key
+        if !self.validators.iter().any(|validator| validator.get_public_key() == public_key) {
+            self.validators.push(ValidatorAccount::new(public_key, activation_height));
+        }
+    }
+
+    fn remove_validator(&mut self, public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE]) {
+        // remove every entry for the public key from the list of validators
+        self.validators.retain(|validator| validator.get_public_key() != public_key);
+    }
+
+    fn get_validator_by_address_mut(&mut self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Option<&mut ValidatorAccount> {
+        let index = self.validators.iter().position(|validator| self.get_address(&validator.get_public_key()) == Some(*address))?;
+        self.validators.get_mut(index)
+    }
+
+    fn undelegate_all(&mut self, public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE], unbond: bool) {
+        // get the stake delegated to the validator
+        let delegations = match self.validators.iter().find(|validator| validator.get_public_key() == public_key) {
+            Some(validator) => validator.get_delegations(),
+            None => return
+        };
+
+        // return each delegation to its delegator, either through the unbonding queue or directly to their balance
+        for delegation in delegations.iter() {
+            let delegator = self.get_or_create_account(delegation.get_delegator());
+            delegator.decrease_delegated(delegation.get_amount());
+
+            if unbond {
+                delegator.increase_unbonding(delegation.get_amount());
+                self.unbonding_queue.push(UnbondingEntry::new(delegation.get_delegator(), delegation.get_amount(), self.get_block_height() + 1 + *UNBONDING_PERIOD));
+            } else {
+                delegator.increase_balance(delegation.get_amount());
+            }
+
+            // decrease the balance of the VALIDATOR_ENABLE_RECIPIENT
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).decrease_balance(delegation.get_amount());
+        }
+
+        if let Some(validator) = self.validators.iter_mut().find(|validator| validator.get_public_key() == public_key) {
+            for delegation in delegations {
+                validator.remove_delegation(delegation.get_delegator(), delegation.get_amount());
+            }
+        }
+    }
+
+    fn pay_transaction_fee(&mut self, validator_address: [u8; BLOCK_ADDRESS_SIZE], proposer: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>, fee: u64, burned_fee: u64) {
+        // burn the burned part of the fee, only the rest (the priority tip) is paid to the block validator and its delegators
+        // the genesis block's transactions don't pay a fee, so never burn more than the fee
+        let burned_fee = burned_fee.min(fee);
+        self.get_or_create_account(*BURN_RECIPIENT).increase_balance(burned_fee);
+        self.credit_block_reward(validator_address, proposer, fee - burned_fee);
+    }
+
+    fn update_deployment_states(&mut self) {
+        // follows BIP9, deployments start signaling at their start height, lock in once DEPLOYMENT_THRESHOLD blocks of a window signal for them, and activate one window after locking in
+        for (index, deployment) in DEPLOYMENTS.iter().enumerate() {
+            let (state, _) = self.deployment_states[index];
+
+            let next_state = match state {
+                DeploymentState::Defined if self.block_height >= deployment.get_timeout_height() => DeploymentState::Failed,
+                DeploymentState::Defined if self.block_height >= deployment.get_start_height() => DeploymentState::Started,
+                DeploymentState::Started if self.get_deployment_signals(index) >= *DEPLOYMENT_THRESHOLD => DeploymentState::LockedIn,
+                DeploymentState::Started if self.block_height >= deployment.get_timeout_height() => DeploymentState::Failed,
+                DeploymentState::LockedIn => DeploymentState::Active,
+                _ => state
+            };
+
+            // the new state applies from the first block of the next window
+            if next_state != state {
+                self.deployment_states[index] = (next_state, self.block_height + 1);
+            }
+        }
+    }
+
+    fn update_loose_change_reward(&mut self) {
+        // every block can recycle 1/LOOSE_CHANGE_RECYCLE_DENOMINATOR of the loose change pool, so the pool is gradually put back into circulation
+        let loose_change = match self.accounts.get(LOOSE_CHANGE_RECIPIENT) {
+            Some(account) => account.get_balance(),
+            None => 0
+        };
+
+        self.loose_change_reward = loose_change / *LOOSE_CHANGE_RECYCLE_DENOMINATOR;
+    }
+
+    fn update_base_fee(&mut self, block: &Block) {
+        // like EIP-1559 the base fee targets blocks that are half full, it increases when blocks are fuller than that and decreases when they are emptier
+        // the change is proportional to how far the block is from the target, at most 1/BASE_FEE_CHANGE_DENOMINATOR of the base fee per block
+        let target = (*MAX_TRANSACTIONS_PER_BLOCK / 2) as u64;
+        let transactions = block.get_weight();
+
+        if transactions > target {
+            let increase = self.base_fee * (transactions - target) / target / *BASE_FEE_CHANGE_DENOMINATOR;
+            // always increase by at least 1 bit so a low base fee can still rise
+            self.base_fee += increase.max(1);
+        } else {
+            let decrease = self.base_fee * (target - transactions) / target / *BASE_FEE_CHANGE_DENOMINATOR;
+            self.base_fee = (self.base_fee - decrease).max(*MINIMUM_BASE_FEE);
+        }
+    }
+
+    fn credit_block_reward(&mut self, recipient: [u8; BLOCK_ADDRESS_SIZE], proposer: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>, amount: u64) {
+        // block rewards (coinbase and transaction fees) are split between the block's recipient and the proposing validator's delegators
+        // the delegators share is proportional to the delegated stake's part of the validator's weight, minus the validator's commission
+        let validator = match proposer.and_then(|proposer| self.validators.iter().find(|validator| validator.get_public_key() == proposer)) {
+            // if the validator lost the rewards for this block then nothing is shared with the delegators
+            Some(validator) if recipient != *LOOSE_CHANGE_RECIPIENT => validator.clone(),
+            _ => {
+                self.get_or_create_account(recipient).increase_balance(amount);
+                return
+            }
+        };
+
+        let delegated_stake = validator.get_delegated_stake();
+        let weight = self.get_validator_weight(&validator);
+
+        if delegated_stake == 0 {
+            self.get_or_create_account(recipient).increase_balance(amount);
+            return
+        }
+
+        // u128 to avoid overflows, the delegators share can never be more than amount
+        let delegators_share = (amount as u128 * delegated_stake as u128 / weight as u128) * (*MAX_VALIDATOR_COMMISSION - validator.get_commission()) as u128 / *MAX_VALIDATOR_COMMISSION as u128;
+
+        // pay each delegator their part of the delegators share
+        let mut paid = 0;
+        for delegation in validator.get_delegations() {
+            let delegator_reward = (delegators_share * delegation.get_amount() as u128 / delegated_stake as u128) as u64;
+            self.get_or_create_account(delegation.get_delegator()).increase_balance(delegator_reward);
+            paid += delegator_reward;
+        }
+
+        // the commission, the validator's own share, and any rounding remainder go to the recipient
+        self.get_or_create_account(recipient).increase_balance(amount - paid);
+    }
+
     fn bootstrapping_phase_end(&mut self) {
-        for (index, validator) in self.get_validators().iter().enumerate() {
+        // validators that are unstaked, removed from the validator list after iterating so indices stay valid
+        let mut unstaked_validators: Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)> = vec![];
+
+        for validator in self.get_validators().iter() {
             // get the validators account on the blockchain to check their stake
-            // get the validators public key
-            let validator_pub_key = match PublicKey::from_sec1_bytes(&validator.get_public_key()) {
-                Ok(validator_pub_key) => validator_pub_key,
+            // get the validators address, its key may have been rotated
+            let account_address = match self.get_address(&validator.get_public_key()) {
+                Some(account_address) => account_address,
                 // should never get here
-                Err(_) => continue
+                None => continue
             };
 
-            // get the address for the account public key
-            let account_address = Wallet::generate_address(&validator_pub_key, true);
-
             // get the validator account
             let validator_account = match self.accounts.get_mut(&account_address) {
                 Some(validator_account) => validator_account,
@@ -491,9 +1316,114 @@ impl Blockchain {
                 validator_account.increase_balance(validator_stake);
                 validator_account.set_stake(0);
                 validator_account.set_validator(false);
-                self.validators.remove(index);
+                unstaked_validators.push((validator.get_public_key(), validator_stake));
+            }
+        }
+
+        // return the stake delegated to the unstaked validators and remove them from the validator list
+        for (public_key, validator_stake) in unstaked_validators {
+            self.get_or_create_account(*VALIDATOR_ENABLE_RECIPIENT).decrease_balance(validator_stake);
+            self.undelegate_all(public_key, false);
+            self.remove_validator(public_key);
+        }
+    }
+
+    fn snapshot_epoch(&mut self) {
+        // the epoch that starts after the current block height, validator changes made during it are only used for choosing proposers in the epoch after
+        // validators still on probation at the start of the epoch aren't included
+        let validators = self.validators.iter().filter(|validator| validator.is_active(self.block_height + 1)).map(|validator| (validator.get_public_key(), self.get_validator_weight(validator))).collect();
+
+        self.epochs.push(Epoch::new(self.block_height / *EPOCH_LENGTH, validators));
+    }
+
+    fn record_supply(&mut self) {
+        let balance = |address| match self.accounts.get(address) {
+            Some(account) => account.get_balance(),
+            None => 0
+        };
+
+        let unbonding = self.unbonding_queue.iter().map(|entry| entry.get_amount()).sum();
+
+        self.supply_history.push(Supply::new(self.block_height, self.issued, balance(VALIDATOR_ENABLE_RECIPIENT), unbonding, balance(BURN_RECIPIENT), balance(LOOSE_CHANGE_RECIPIENT)));
+    }
+
+    pub fn is_supply_conserved(&self) -> bool {
+        // every amount ever issued is either in an account balance (including the staking, burn and loose change pools) or waiting in the unbonding queue
+        let balances: u128 = self.accounts.values().map(|account| account.get_balance() as u128).sum();
+        let unbonding: u128 = self.unbonding_queue.iter().map(|entry| entry.get_amount() as u128).sum();
+
+        balances + unbonding == self.issued as u128
+    }
+
+    fn update_randomness_beacon(&mut self, block: &Block) -> bool {
+        // blocks before VRF_BLOCK_VERSION don't carry a vrf proof, only the genesis block can be such a block
+        if block.get_format_version() < *VRF_BLOCK_VERSION {
+            self.randomness_beacon = block.serialize_hash_block_header().try_into().unwrap();
+            return true
+        }
+
+        // get the vrf output of the block's proof, the proof was verified by the verification_engine
+        let vrf_output = match vrf::proof_to_hash(&block.get_vrf_proof()) {
+            Some(vrf_output) => vrf_output,
+            None => return false
+        };
+
+        // randomness_beacon = sha256(randomness_beacon + vrf_output)
+        let mut sha256_hasher: Sha256 = Sha256::new();
+        sha256_hasher.update(self.randomness_beacon);
+        sha256_hasher.update(vrf_output);
+        self.randomness_beacon = sha256_hasher.finalize().into();
+
+        true
+    }
+
+    fn release_unbonded_stake(&mut self) {
+        let block_height = self.get_block_height();
+
+        // release every unbonding entry that has reached its release height back to the account's balance
+        for entry in self.unbonding_queue.iter() {
+            if entry.get_release_height() > block_height {
+                continue;
             }
+
+            match self.accounts.get_mut(&entry.get_address()) {
+                Some(account) => {
+                    account.decrease_unbonding(entry.get_amount());
+                    account.increase_balance(entry.get_amount());
+                },
+                // Should never get here, the account was created when the stake was revoked
+                None => continue
+            };
+        }
+
+        // remove the released entries from the unbonding queue
+        self.unbonding_queue.retain(|entry| {
+            entry.get_release_height() > block_height
+        });
+    }
+
+    fn settle_closed_payment_channels(&mut self) {
+        let block_height = self.get_block_height();
+
+        // settle every channel closed by its payer that the payee didn't close with a later update before its settle height
+        let settled_channels: Vec<PaymentChannel> = self.payment_channels.values().filter(|channel| channel.get_settle_height().is_some_and(|settle_height| settle_height <= block_height)).cloned().collect();
+        for channel in settled_channels.iter() {
+            self.settle_payment_channel(channel, channel.get_paid());
         }
+
+        // remove the settled channels from the registry
+        self.payment_channels.retain(|_, channel| {
+            channel.get_settle_height().is_none_or(|settle_height| settle_height > block_height)
+        });
+    }
+
+    fn settle_payment_channel(&mut self, channel: &PaymentChannel, paid: u64) {
+        // pay the payee its part of the deposit and return the rest to the payer
+        self.get_or_create_account(channel.get_payee()).increase_balance(paid);
+        self.get_or_create_account(channel.get_payer()).increase_balance(channel.get_deposit() - paid);
+
+        // decrease the balance of the CHANNEL_RECIPIENT
+        self.get_or_create_account(*CHANNEL_RECIPIENT).decrease_balance(channel.get_deposit());
     }
 
     pub fn get_block(&self, block_height: u64) -> Option<Block> {
@@ -509,10 +1439,125 @@ impl Blockchain {
         self.block_height
     }
 
+    pub fn get_transaction(&self, txid: &[u8]) -> Option<(Transaction, u64)> {
+        // search the blocks from the most recent, the transaction id is sha256(serialized transaction without its signatures)
+        self.blocks.iter().enumerate().rev().find_map(|(block_height, block)| {
+            block.get_transactions().iter()
+                .find(|transaction| transaction.serialize_hash_txid() == txid)
+                .map(|transaction| (transaction.clone(), block_height as u64))
+        })
+    }
+
+    pub fn get_current_epoch(&self) -> Option<Epoch> {
+        self.epochs.last().cloned()
+    }
+
+    pub fn get_current_epoch_validators(&self) -> Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)> {
+        self.epochs.last().map(|epoch| epoch.get_validators()).unwrap_or_default()
+    }
+
+    pub fn get_epoch(&self, number: u64) -> Option<Epoch> {
+        self.epochs.get(number as usize).cloned()
+    }
+
+    pub fn get_supply(&self, block_height: u64) -> Option<Supply> {
+        self.supply_history.get(block_height as usize).cloned()
+    }
+
+    pub fn get_trusted_checkpoint(&self, block_height: u64) -> Option<[u8; 32]> {
+        self.trusted_checkpoints.iter().find(|(checkpoint_height, _)| *checkpoint_height == block_height).map(|(_, block_hash)| *block_hash)
+    }
+
+    pub fn get_last_trusted_checkpoint_height(&self) -> u64 {
+        self.trusted_checkpoints.iter().map(|(checkpoint_height, _)| *checkpoint_height).max().unwrap_or(0)
+    }
+
+    pub fn get_validator_enables(&self) -> usize {
+        self.validator_enables
+    }
+
+    pub fn get_finalized_height(&self) -> u64 {
+        self.finalized_height
+    }
+
+    pub fn is_block_final(&self, block_height: u64) -> bool {
+        block_height <= self.finalized_height
+    }
+
+    pub fn get_latest_checkpoint_height(&self) -> u64 {
+        self.block_height - self.block_height % *CHECKPOINT_INTERVAL
+    }
+
+    pub fn get_attestations(&self, block_height: u64) -> Vec<Attestation> {
+        self.attestations.get(&block_height).cloned().unwrap_or_default()
+    }
+
+    pub fn get_fork_height(&self, other: &Blockchain) -> u64 {
+        // the first block height at which the two blockchains differ, if one blockchain extends the other this is the height after the shorter one
+        let common_height = self.block_height.min(other.get_block_height());
+
+        for block_height in 0..=common_height {
+            if self.blocks[block_height as usize] != other.blocks[block_height as usize] {
+                return block_height
+            }
+        }
+
+        common_height + 1
+    }
+
+    pub fn is_reorg_allowed(&self, other: &Blockchain) -> bool {
+        // a blockchain can only be replaced by one that keeps every final block
+        self.get_fork_height(other) > self.finalized_height
+    }
+
+    pub fn get_randomness_beacon(&self) -> [u8; 32] {
+        self.randomness_beacon
+    }
+
+    pub fn get_signed_chain_id(&self) -> [u8; 32] {
+        // transactions, blocks and channel updates of a format that signs the chain id sign all zeros until the chain_id deployment is active
+        if self.is_deployment_active("chain_id") {
+            self.chain_id
+        } else {
+            [0x00; 32]
+        }
+    }
+
     pub fn get_account(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Option<&Account> {
         self.accounts.get(address)
     }
 
+    pub fn get_address(&self, public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE]) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        // a key an account has been rotated to controls that account
+        if let Some(address) = self.rotated_keys.get(public_key) {
+            return Some(*address)
+        }
+
+        // any other key controls the address generated from it
+        Self::get_generated_address(public_key)
+    }
+
+    fn get_generated_address(public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE]) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        match PublicKey::from_sec1_bytes(public_key) {
+            Ok(public_key) => Some(Wallet::generate_address(&public_key, true)),
+            Err(_) => None
+        }
+    }
+
+    pub fn is_rotated_key_address(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> bool {
+        // funds sent to the address generated from a key that controls another account could never be spent
+        self.rotated_key_addresses.contains(address)
+    }
+
+    pub fn get_sender_address(&self, transaction: &Transaction) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        // multisig accounts are identified by their keys and threshold, they can't be rotated
+        if transaction.multisig.is_some() {
+            return transaction.get_sender_address()
+        }
+
+        self.get_address(&transaction.sender)
+    }
+
     pub fn get_last_block(&self) -> &Block {
         self.blocks.last().unwrap()
     }
@@ -529,11 +1574,76 @@ impl Blockchain {
         self.validators.clone()
     }
 
-    pub fn get_mempool(&mut self) -> &mut HashMap<[u8; COMPRESSED_PUBLIC_KEY_SIZE], Vec<Transaction>> {
+    pub fn get_validator_by_address(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Option<ValidatorAccount> {
+        // find the validator whose public key controls the given address
+        self.validators.iter().find(|validator| self.get_address(&validator.get_public_key()) == Some(*address)).cloned()
+    }
+
+    pub fn get_delegations(&self, delegator: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<([u8; BLOCK_ADDRESS_SIZE], u64)> {
+        // get every validator address the delegator has delegated to and the amount delegated
+        let mut delegations = vec![];
+
+        for validator in self.validators.iter() {
+            let amount = validator.get_delegation(delegator);
+            if amount == 0 {
+                continue
+            }
+
+            if let Some(validator_address) = self.get_address(&validator.get_public_key()) {
+                delegations.push((validator_address, amount));
+            }
+        }
+
+        delegations
+    }
+
+    pub fn get_unbonding_queue(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<UnbondingEntry> {
+        // get all of the unbonding entries belonging to the given address
+        self.unbonding_queue.iter().filter(|entry| entry.get_address() == *address).cloned().collect()
+    }
+
+    pub fn get_htlc(&self, lock_id: &[u8; 32]) -> Option<&Htlc> {
+        self.htlcs.get(lock_id)
+    }
+
+    pub fn get_htlcs(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<([u8; 32], Htlc)> {
+        // get all of the hash time-locked transfers the given address has locked or can claim
+        self.htlcs.iter().filter(|(_, htlc)| htlc.get_sender() == *address || htlc.get_recipient() == *address).map(|(lock_id, htlc)| (*lock_id, htlc.clone())).collect()
+    }
+
+    pub fn get_payment_channel(&self, channel_id: &[u8; 32]) -> Option<&PaymentChannel> {
+        self.payment_channels.get(channel_id)
+    }
+
+    pub fn get_payment_channels(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<([u8; 32], PaymentChannel)> {
+        // get all of the payment channels the given address pays or is paid by
+        self.payment_channels.iter().filter(|(_, channel)| channel.get_payer() == *address || channel.get_payee() == *address).map(|(channel_id, channel)| (*channel_id, channel.clone())).collect()
+    }
+
+    pub fn get_name_record(&self, name: &[u8; MAX_NAME_SIZE]) -> Option<&NameRecord> {
+        self.names.get(name)
+    }
+
+    pub fn resolve_name(&self, name: &[u8; MAX_NAME_SIZE]) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        // an expired name no longer resolves, the block the next transaction is added to is at block_height + 1
+        match self.names.get(name) {
+            Some(name_record) if !name_record.is_expired(self.block_height + 1) => Some(name_record.get_owner()),
+            _ => None
+        }
+    }
+
+    pub fn get_names(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<[u8; MAX_NAME_SIZE]> {
+        // reverse lookup of every unexpired name that resolves to the given address, sorted so the result is stable
+        let mut names: Vec<[u8; MAX_NAME_SIZE]> = self.names.iter().filter(|(_, name_record)| name_record.get_owner() == *address && !name_record.is_expired(self.block_height + 1)).map(|(name, _)| *name).collect();
+        names.sort();
+        names
+    }
+
+    pub fn get_mempool(&mut self) -> &mut HashMap<[u8; BLOCK_ADDRESS_SIZE], Vec<Transaction>> {
         &mut self.mempool
     }
 
-    pub fn get_mempool_clone(&self) -> HashMap<[u8; COMPRESSED_PUBLIC_KEY_SIZE], Vec<Transaction>> {
+    pub fn get_mempool_clone(&self) -> HashMap<[u8; BLOCK_ADDRESS_SIZE], Vec<Transaction>> {
         self.mempool.clone()
     }
 
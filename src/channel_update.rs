@@ -0,0 +1,68 @@
+use bincode::{Options, ErrorKind};
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+
+use crate::signature::Signature;
+
+// An off-chain payment channel balance update, the payer's promise that the payee can close the channel with paid of its deposit
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelUpdate {
+    // id of the transaction that opened the channel
+    channel_id: [u8; 32],
+    // total amount of the deposit paid to the payee so far, every new update pays more than the last
+    paid: u64,
+    // sign(sha256(channel_id + paid + chain_id)) by the payer
+    signature: Signature,
+}
+
+impl ChannelUpdate {
+    pub fn new(channel_id: [u8; 32], paid: u64, signature: Signature) -> Self {
+        Self {
+            channel_id,
+            paid,
+            signature,
+        }
+    }
+
+    pub fn from(raw: Vec<u8>) -> Result<Self, Box<ErrorKind>> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .deserialize(&raw)
+    }
+
+    pub fn serialize_channel_update(&self) -> Vec<u8> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize(self).unwrap()
+    }
+
+    pub fn get_channel_id(&self) -> [u8; 32] {
+        self.channel_id
+    }
+
+    pub fn get_paid(&self) -> u64 {
+        self.paid
+    }
+
+    pub fn get_signature(&self) -> Signature {
+        self.signature
+    }
+
+    pub fn serialize_hash_update_payload(channel_id: [u8; 32], paid: u64, chain_id: [u8; 32]) -> Vec<u8> {
+        // serialize the channel id, paid amount and chain id, this is what the payer signs so an update can't be replayed on another network
+        let serialized_update_payload = bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize(&(channel_id, paid, chain_id)).unwrap();
+
+        // sha256(serialized update payload)
+        let mut sha256_hasher: Sha256 = Sha256::new();
+        sha256_hasher.update(serialized_update_payload);
+        sha256_hasher.finalize().to_vec()
+    }
+}
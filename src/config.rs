@@ -4,13 +4,16 @@ use std::path::{PathBuf, Path};
 use serde::{Serialize, Deserialize};
 
 use crate::constants::DEFAULT_CONFIG_OPTIONS_STRING;
-use crate::util::{create_file_new, open_file_read, read_file_from_beginning};
+use crate::util::{create_file_new, decode_hex, open_file_read, read_file_from_beginning};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
     wallet: WalletConfig,
     validator: ValidatorConfig,
-    network: NetworkConfig
+    network: NetworkConfig,
+    // optional so config files created before this section existed still parse
+    #[serde(default)]
+    blockchain: BlockchainConfig
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -37,6 +40,15 @@ pub struct NetworkConfig {
     peer_list: Vec<String>
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BlockchainConfig {
+    // whether or not to check that the total supply is conserved after every block is added, this is slow on large blockchains
+    verify_supply: bool,
+    // additional trusted checkpoints as "height:header hash" strings, the header hash is in hex
+    #[serde(default)]
+    trusted_checkpoints: Vec<String>,
+}
+
 impl Config {
     pub fn new(config_file_path: &Path) -> Self {
         // attempt to open config file, create it if it doesnt exist, or exit if other error
@@ -93,6 +105,10 @@ impl Config {
     pub fn get_network_config(&self) -> NetworkConfig {
         self.network.clone()
     }
+
+    pub fn get_blockchain_config(&self) -> BlockchainConfig {
+        self.blockchain.clone()
+    }
     
 }
 
@@ -124,4 +140,20 @@ impl NetworkConfig {
     pub fn get_peer_list(&self) -> Vec<String> {
         self.peer_list.clone()
     }
+}
+
+impl BlockchainConfig {
+    pub fn get_verify_supply(&self) -> bool {
+        self.verify_supply
+    }
+
+    pub fn get_trusted_checkpoints(&self) -> Vec<(u64, [u8; 32])> {
+        // invalid entries are ignored
+        self.trusted_checkpoints.iter().filter_map(|checkpoint| {
+            let (block_height, block_hash) = checkpoint.split_once(':')?;
+            let block_height = block_height.trim().parse::<u64>().ok()?;
+            let block_hash: [u8; 32] = decode_hex(block_hash.trim())?.try_into().ok()?;
+            Some((block_height, block_hash))
+        }).collect()
+    }
 }
\ No newline at end of file
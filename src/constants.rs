@@ -1,3 +1,5 @@
+use crate::deployment::Deployment;
+
 // version of the block node software
 pub const NODE_VERSION: &'static str = "0.1.0";
 // version of the block api
@@ -14,6 +16,34 @@ pub const WIF_VERSION1_PREFIX_BYTES: &'static [u8; 1] = &[0x80];
 pub const WIF_VERSION1_COMPRESSED_BYTES: &'static [u8; 1] = &[0x01];
 // version bytes used to indicate transaction version
 pub const TRANSACTION_VERSION: &'static u8 = &0x01;
+// first transaction version that carries type specific transaction data (delegation, commission)
+pub const DATA_TRANSACTION_VERSION: &'static u8 = &0x02;
+// first transaction version that can be sent from a multisig account, these transactions also sign the sender's address
+pub const MULTISIG_TRANSACTION_VERSION: &'static u8 = &0x03;
+// first transaction version that can carry a validity window, a block height or timestamp range the transaction can be added to a block in
+pub const VALIDITY_WINDOW_TRANSACTION_VERSION: &'static u8 = &0x04;
+// first transaction version that can carry a memo, a free-form payment reference
+pub const MEMO_TRANSACTION_VERSION: &'static u8 = &0x05;
+// first transaction version that can pay a list of outputs from a single batch transaction
+pub const BATCH_TRANSACTION_VERSION: &'static u8 = &0x06;
+// first transaction version whose signature covers the chain id, so it can't be replayed on another network
+pub const CHAIN_ID_TRANSACTION_VERSION: &'static u8 = &0x07;
+// first transaction version that can be signed with a Schnorr signature, its signature is serialized with its signature scheme
+pub const SCHNORR_TRANSACTION_VERSION: &'static u8 = &0x08;
+// first transaction version that can lock, claim and refund hash time-locked transfers
+pub const HTLC_TRANSACTION_VERSION: &'static u8 = &0x09;
+// first transaction version that can open and close payment channels
+pub const CHANNEL_TRANSACTION_VERSION: &'static u8 = &0x0A;
+// first transaction version that can rotate the public key controlling an account
+pub const KEY_ROTATION_TRANSACTION_VERSION: &'static u8 = &0x0B;
+// first transaction version that can register, renew and transfer names
+pub const NAME_TRANSACTION_VERSION: &'static u8 = &0x0C;
+// signature scheme of secp256k1 ECDSA signatures
+pub const ECDSA_SIGNATURE_SCHEME: &'static u8 = &0x00;
+// signature scheme of BIP340 Schnorr signatures
+pub const SCHNORR_SIGNATURE_SCHEME: &'static u8 = &0x01;
+// size of an ECDSA or Schnorr signature in bytes
+pub const SIGNATURE_SIZE: usize = 64;
 // block version 1 address size in bytes
 pub const BLOCK_ADDRESS_SIZE: usize = 39;
 // block version 1 wif private key size in bytes
@@ -33,9 +63,22 @@ propose_without_coinbase = true
 [network]
 local_blockchain = true
 peer_list = ["127.0.0.1:28475"]
+[blockchain]
+verify_supply = false
+trusted_checkpoints = []
 "#;
-// version bytes used to indicate block version
-pub const BLOCK_VERSION: &'static u32 = &0x01;
+// version bytes used to indicate block version, this is the block format version new blocks are created with
+pub const BLOCK_VERSION: &'static u32 = &0x02;
+// the low 8 bits of a block header version are the block format version, the upper bits are used by validators to signal for deployments
+pub const BLOCK_FORMAT_VERSION_MASK: &'static u32 = &0xFF;
+// first block version whose header carries a vrf proof for the randomness beacon
+pub const VRF_BLOCK_VERSION: &'static u32 = &0x02;
+// first block version whose signature covers the chain id, so it can't be replayed on another network
+pub const CHAIN_ID_BLOCK_VERSION: &'static u32 = &0x03;
+// first block version that can be signed with a Schnorr signature, its signature is serialized with its signature scheme
+pub const SCHNORR_BLOCK_VERSION: &'static u32 = &0x04;
+// size of a vrf proof in bytes, compressed point + 2 scalars
+pub const VRF_PROOF_SIZE: usize = 97;
 // coinbase transaction sender
 pub const COINBASE_SENDER: &'static [u8; COMPRESSED_PUBLIC_KEY_SIZE] = &[0x00; COMPRESSED_PUBLIC_KEY_SIZE];
 // account that receieves transaction fees for blocks that don't have a proper validator (no coinbase transaction was sent)
@@ -44,6 +87,40 @@ pub const LOOSE_CHANGE_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x
 pub const VALIDATOR_ENABLE_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
 // validator revoke transaction recipient
 pub const VALIDATOR_REVOKE_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02];
+// validator stake increase transaction recipient
+pub const VALIDATOR_STAKE_INCREASE_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03];
+// validator stake withdraw transaction recipient
+pub const VALIDATOR_STAKE_WITHDRAW_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04];
+// validator commission transaction recipient
+pub const VALIDATOR_COMMISSION_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05];
+// sender of transactions from multisig accounts, the signing keys are carried by the transaction's multisig witness
+pub const MULTISIG_SENDER: &'static [u8; COMPRESSED_PUBLIC_KEY_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01];
+// maximum number of public keys of a multisig account
+pub const MAX_MULTISIG_PUBLIC_KEYS: usize = 16;
+// maximum size of a transaction memo in bytes
+pub const MAX_MEMO_SIZE: usize = 256;
+// minimum size of a registered name in bytes
+pub const MIN_NAME_SIZE: usize = 3;
+// maximum size of a registered name in bytes, shorter names are padded with zeros
+pub const MAX_NAME_SIZE: usize = 32;
+// batch transaction recipient, the funds are paid to the transaction's outputs
+pub const BATCH_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07];
+// hash time-locked transfer claim and refund recipient, locked funds are held by this address until they are claimed or refunded
+pub const HTLC_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08];
+// payment channel close recipient, deposited funds are held by this address until the channel is settled
+pub const CHANNEL_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09];
+// key rotation recipient, the transaction's data holds the new public key of the sending account
+pub const KEY_ROTATION_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0A];
+// name registration and renewal recipient, the registration fee is burned
+pub const NAME_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0B];
+// maximum number of outputs of a batch transaction
+pub const MAX_BATCH_OUTPUTS: usize = 1000;
+// account that receives burned funds, no one can spend from it
+pub const BURN_RECIPIENT: &'static [u8; BLOCK_ADDRESS_SIZE] = &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06];
+// commission a validator keeps from the rewards owed to its delegators until it sets its own, in basis points (10%)
+pub const DEFAULT_VALIDATOR_COMMISSION: &'static u16 = &1000;
+// maximum validator commission in basis points (100%)
+pub const MAX_VALIDATOR_COMMISSION: &'static u16 = &10000;
 // serialized genesis block
 // ToDo: update this structure
 // Block {
@@ -75,5 +152,63 @@ pub const LOWEST_DENOMINATION_PER_COIN: &'static f64 = &100000000.0;
 pub const BOOTSTRAPPING_PHASE_BLOCK_HEIGHT: &'static u64 = &105000;
 // minimum amount that needs to be staked to become a validator 32 coins, only used after the bootstrapping phase
 pub const MINIMUM_STAKING_AMOUNT: &'static u64 = &3200000000;
-// maximum transactions allowed per block
+// maximum transactions allowed per block, a batch transaction counts once for each of its outputs
 pub const MAX_TRANSACTIONS_PER_BLOCK: &'static usize = &3000;
+// number of blocks revoked stake stays locked in the unbonding queue before it is released to the account balance (~1 week)
+pub const UNBONDING_PERIOD: &'static u64 = &2016;
+// number of blocks the payee of a payment channel closed by its payer has to close it with a later update before it is settled (~1 day)
+pub const CHANNEL_DISPUTE_PERIOD: &'static u64 = &288;
+// number of blocks a name stays registered for after it is registered or renewed (~1 year)
+pub const NAME_REGISTRATION_PERIOD: &'static u64 = &105120;
+// minimum amount burned to register or renew a name for NAME_REGISTRATION_PERIOD blocks, 1 coin
+pub const NAME_REGISTRATION_FEE: &'static u64 = &100000000;
+// validators attest to every CHECKPOINT_INTERVAL'th block, a checkpoint is final once validators holding 2/3 of the stake have attested to it
+pub const CHECKPOINT_INTERVAL: &'static u64 = &32;
+// trusted (block height, block header hash) checkpoints of the network started from GENESIS_BLOCK, chains that conflict with them are rejected and blocks at or below the last one skip the proposer schedule search
+// the network has no checkpoints past the genesis block yet, nodes can add their own with the trusted_checkpoints config option
+pub const TRUSTED_CHECKPOINTS: &'static [(u64, [u8; 32])] = &[];
+// the validator set used to choose block proposers only changes every EPOCH_LENGTH blocks, BOOTSTRAPPING_PHASE_BLOCK_HEIGHT is a multiple of it so the bootstrapping phase ends on an epoch boundary
+pub const EPOCH_LENGTH: &'static u64 = &120;
+// minimum fee of a validator enable transaction during the bootstrapping phase (1 coin), this part of the fee is burned so registering many validators isn't free
+pub const MINIMUM_VALIDATOR_ENABLE_FEE: &'static u64 = &100000000;
+// maximum number of validator enable transactions allowed per block
+pub const MAX_VALIDATOR_ENABLES_PER_BLOCK: &'static usize = &4;
+// number of blocks a new validator has to wait before it can be chosen as a block proposer (~1 day), validators in the genesis block don't have to wait
+pub const VALIDATOR_PROBATION_PERIOD: &'static u64 = &288;
+// lowest the base fee (minimum fee of every transaction, burned) can go, in bits
+pub const MINIMUM_BASE_FEE: &'static u64 = &1000;
+// the base fee changes by at most 1/BASE_FEE_CHANGE_DENOMINATOR per block
+pub const BASE_FEE_CHANGE_DENOMINATOR: &'static u64 = &8;
+// a transaction replacing a pending transaction with the same sender and nonce must pay at least 1/REPLACEMENT_FEE_INCREASE_DENOMINATOR (10%) more fee, and at least the base fee more
+pub const REPLACEMENT_FEE_INCREASE_DENOMINATOR: &'static u64 = &10;
+// every block can recycle 1/LOOSE_CHANGE_RECYCLE_DENOMINATOR of the loose change pool (0.1%) on top of the block subsidy
+pub const LOOSE_CHANGE_RECYCLE_DENOMINATOR: &'static u64 = &1000;
+// number of blocks in a deployment signaling window (~1 week), deployments only change state at the end of a window
+pub const DEPLOYMENT_WINDOW: &'static u64 = &2016;
+// number of blocks within a window that must signal for a deployment to lock it in (75%)
+pub const DEPLOYMENT_THRESHOLD: &'static u64 = &1512;
+// protocol upgrades validators can signal for, each deployment must use a different bit
+// testdummy doesn't change any rules, it exercises signaling and activation
+pub const DEPLOYMENTS: &'static [Deployment] = &[
+    Deployment::new("testdummy", 28, 0, 1050000, 0, 0),
+    // allows transactions from m of n multisig accounts
+    Deployment::new("multisig", 8, 0, 1050000, 0, *MULTISIG_TRANSACTION_VERSION),
+    // allows transactions with a validity window that expire if they aren't added to a block in time
+    Deployment::new("validity_window", 9, 0, 1050000, 0, *VALIDITY_WINDOW_TRANSACTION_VERSION),
+    // allows transactions with a memo
+    Deployment::new("memo", 10, 0, 1050000, 0, *MEMO_TRANSACTION_VERSION),
+    // allows batch transactions paying multiple recipients
+    Deployment::new("batch", 11, 0, 1050000, 0, *BATCH_TRANSACTION_VERSION),
+    // signs transactions and blocks for a single network, identified by its genesis block header hash
+    Deployment::new("chain_id", 12, 0, 1050000, *CHAIN_ID_BLOCK_VERSION, *CHAIN_ID_TRANSACTION_VERSION),
+    // allows transactions and blocks signed with BIP340 Schnorr signatures
+    Deployment::new("schnorr", 13, 0, 1050000, *SCHNORR_BLOCK_VERSION, *SCHNORR_TRANSACTION_VERSION),
+    // allows hash time-locked transfers for atomic swaps
+    Deployment::new("htlc", 14, 0, 1050000, 0, *HTLC_TRANSACTION_VERSION),
+    // allows unidirectional payment channels settled with off-chain channel updates
+    Deployment::new("payment_channel", 15, 0, 1050000, 0, *CHANNEL_TRANSACTION_VERSION),
+    // allows rotating the public key of an account without moving its funds
+    Deployment::new("key_rotation", 16, 0, 1050000, 0, *KEY_ROTATION_TRANSACTION_VERSION),
+    // allows registering human-readable names that resolve to addresses
+    Deployment::new("name_registry", 17, 0, 1050000, 0, *NAME_TRANSACTION_VERSION),
+];
@@ -3,21 +3,31 @@ use std::env;
 use std::time::SystemTime;
 use std::path::PathBuf;
 
-use k256::PublicKey;
-
 use crate::account::Account;
+use crate::attestation::Attestation;
 use crate::block::Block;
+use crate::channel_update::ChannelUpdate;
 use crate::blockchain::Blockchain;
-use crate::config::{Config, NetworkConfig, ValidatorConfig, WalletConfig};
+use crate::config::{BlockchainConfig, Config, NetworkConfig, ValidatorConfig, WalletConfig};
+use crate::deployment::DeploymentState;
+use crate::epoch::Epoch;
+use crate::htlc::Htlc;
+use crate::multisig::Multisig;
+use crate::name_record::NameRecord;
 use crate::network::{Network, Peer};
+use crate::payment_channel::PaymentChannel;
+use crate::supply::Supply;
 use crate::transaction::Transaction;
+use crate::tx_format::TxFormat;
+use crate::tx_options::TxOptions;
+use crate::unbonding_entry::UnbondingEntry;
 use crate::util::read_string;
 use crate::validator_account::ValidatorAccount;
 use crate::validator::Validator;
 use crate::verification_engine;
 use crate::wallet::Wallet;
 
-use crate::constants::{BLOCK_ADDRESS_SIZE, BLOCK_VERSION, COMPRESSED_PUBLIC_KEY_SIZE, DEFAULT_CONFIG_FILE_NAME, GENESIS_BLOCK, LOOSE_CHANGE_RECIPIENT, LOWEST_DENOMINATION_PER_COIN, MAX_TRANSACTIONS_PER_BLOCK, VALIDATOR_ENABLE_RECIPIENT};
+use crate::constants::{BLOCK_ADDRESS_SIZE, BLOCK_VERSION, CHECKPOINT_INTERVAL, COMPRESSED_PUBLIC_KEY_SIZE, DEFAULT_CONFIG_FILE_NAME, DEPLOYMENTS, GENESIS_BLOCK, LOOSE_CHANGE_RECIPIENT, LOWEST_DENOMINATION_PER_COIN, MAX_NAME_SIZE, MAX_TRANSACTIONS_PER_BLOCK, MAX_VALIDATOR_ENABLES_PER_BLOCK, VALIDATOR_ENABLE_RECIPIENT};
 
 pub struct Controller {
     config: Config,
@@ -77,7 +87,7 @@ impl Controller {
 
         // initialize blockchain
         println!("Initializing blockchain");
-        let mut blockchain = Blockchain::new();
+        let mut blockchain = Blockchain::new(config.get_blockchain_config());
         println!("Initialized blockchain");
         println!();
 
@@ -110,7 +120,7 @@ impl Controller {
             // create initial coinbase transaction
             let genesis_coinbase_tx = wallet.create_coinbase_tx(verification_engine::get_block_subsidy(0), wallet.get_address()).unwrap();
             // create initial validator enable transaction
-            let genesis_validator_enable_tx = wallet.create_validator_enable_tx(0, 0).unwrap();
+            let genesis_validator_enable_tx = wallet.create_validator_enable_tx(0, 0, TxFormat::new([0x00; 32], false, false)).unwrap();
             // add the initial validator enable transaction to the genesis block transaction vector
             let mut genesis_tx_vec = vec![genesis_coinbase_tx, genesis_validator_enable_tx];
             // get the current timestamp
@@ -118,8 +128,17 @@ impl Controller {
                 Ok(timestamp) => timestamp.as_secs(),
                 Err(_) => return None,
             };
-            // get the local blockchain genesis block signature
-            let genesis_sig = match wallet.create_block_sig(*BLOCK_VERSION, [0x00; 32], timestamp, &genesis_tx_vec) {
+            // the genesis block has no previous randomness beacon, so the vrf proof is over the genesis previous hash
+            let genesis_vrf_proof = match wallet.create_vrf_proof(&[0x00; 32]) {
+                Some(genesis_vrf_proof) => genesis_vrf_proof,
+                None => {
+                    println!("Unable to create local blockchain genesis block vrf proof, check your wallet file");
+                    println!();
+                    return None
+                }
+            };
+            // get the local blockchain genesis block signature, the genesis block's header hash becomes the chain id so it can't sign one
+            let genesis_sig = match wallet.create_block_sig(*BLOCK_VERSION, [0x00; 32], timestamp, genesis_vrf_proof, &genesis_tx_vec, [0x00; 32]) {
                 Some(genesis_sig) => genesis_sig,
                 None => {
                     println!("Unable to sign local blockchain genesis block, check your wallet file");
@@ -128,7 +147,7 @@ impl Controller {
                 }
             };
             // create the local blockchain genesis block
-            let genesis_block = validator.create_block(&mut genesis_tx_vec, [0x00; 32], timestamp, genesis_sig);
+            let genesis_block = validator.create_block(*BLOCK_VERSION, &mut genesis_tx_vec, [0x00; 32], timestamp, genesis_vrf_proof, genesis_sig);
 
             // increment the wallet nonce to account for the validator enable transaction
             wallet.increment_nonce();
@@ -180,7 +199,15 @@ impl Controller {
         println!("Wallet:");
         println!("\tAddress: {}", self.wallet.get_address_string());
         println!("\tBalance: {:.8}", self.wallet_get_balance());
+        println!("\tStake: {:.8}", self.wallet_get_stake());
+        println!("\tUnbonding: {:.8}", self.wallet_get_unbonding());
+        println!("\tDelegated: {:.8}", self.wallet_get_delegated());
         println!("\tNonce: {}", self.wallet_get_nonce());
+        println!("\tNames: {}", self.blockchain_get_names(&self.wallet_get_address()).join(", "));
+    }
+
+    pub fn wallet_get_public_key(&self) -> [u8; COMPRESSED_PUBLIC_KEY_SIZE] {
+        self.wallet.get_public_key().to_sec1_bytes().to_vec().try_into().unwrap()
     }
 
     pub fn wallet_get_address(&self) -> [u8;39] {
@@ -198,6 +225,27 @@ impl Controller {
         }
     }
 
+    pub fn wallet_get_stake(&self) -> f64 {
+        match self.blockchain.get_account(&self.wallet_get_address()) {
+            Some(account) => account.get_stake() as f64 / LOWEST_DENOMINATION_PER_COIN,
+            None => 0.0
+        }
+    }
+
+    pub fn wallet_get_unbonding(&self) -> f64 {
+        match self.blockchain.get_account(&self.wallet_get_address()) {
+            Some(account) => account.get_unbonding() as f64 / LOWEST_DENOMINATION_PER_COIN,
+            None => 0.0
+        }
+    }
+
+    pub fn wallet_get_delegated(&self) -> f64 {
+        match self.blockchain.get_account(&self.wallet_get_address()) {
+            Some(account) => account.get_delegated() as f64 / LOWEST_DENOMINATION_PER_COIN,
+            None => 0.0
+        }
+    }
+
     pub fn wallet_get_nonce(&self) -> u64 {
         self.wallet.get_nonce()
     }
@@ -229,11 +277,21 @@ impl Controller {
         self.blockchain.get_block(block_height)
     }
 
+    pub fn blockchain_get_transaction(&self, txid: &[u8]) -> Option<(Transaction, Option<u64>)> {
+        // look for the transaction in the blockchain first, then in the mempool
+        match self.blockchain.get_transaction(txid) {
+            Some((transaction, block_height)) => Some((transaction, Some(block_height))),
+            None => self.blockchain.get_mempool_clone().into_values().flatten()
+                .find(|transaction| transaction.serialize_hash_txid() == txid)
+                .map(|transaction| (transaction, None))
+        }
+    }
+
     pub fn blockchain_get_account(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Option<&Account> {
         self.blockchain.get_account(address)
     }
 
-    pub fn blockchain_get_mempool(&self) -> HashMap<[u8; COMPRESSED_PUBLIC_KEY_SIZE], Vec<Transaction>> {
+    pub fn blockchain_get_mempool(&self) -> HashMap<[u8; BLOCK_ADDRESS_SIZE], Vec<Transaction>> {
         self.blockchain.get_mempool_clone()
     }
 
@@ -241,6 +299,50 @@ impl Controller {
         self.blockchain.get_validators()
     }
 
+    pub fn blockchain_get_unbonding_queue(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<UnbondingEntry> {
+        self.blockchain.get_unbonding_queue(address)
+    }
+
+    pub fn blockchain_get_name_record(&self, name: &[u8; MAX_NAME_SIZE]) -> Option<NameRecord> {
+        self.blockchain.get_name_record(name).cloned()
+    }
+
+    pub fn blockchain_resolve_name(&self, name: &str) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        // anything that isn't a valid name, such as an address, doesn't resolve
+        match NameRecord::encode_name(name) {
+            Some(name) => self.blockchain.resolve_name(&name),
+            None => None
+        }
+    }
+
+    pub fn blockchain_get_names(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<String> {
+        self.blockchain.get_names(address).iter().map(NameRecord::decode_name).collect()
+    }
+
+    pub fn blockchain_get_htlc(&self, lock_id: &[u8; 32]) -> Option<Htlc> {
+        self.blockchain.get_htlc(lock_id).cloned()
+    }
+
+    pub fn blockchain_get_htlcs(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<([u8; 32], Htlc)> {
+        self.blockchain.get_htlcs(address)
+    }
+
+    pub fn blockchain_get_payment_channel(&self, channel_id: &[u8; 32]) -> Option<PaymentChannel> {
+        self.blockchain.get_payment_channel(channel_id).cloned()
+    }
+
+    pub fn blockchain_get_payment_channels(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<([u8; 32], PaymentChannel)> {
+        self.blockchain.get_payment_channels(address)
+    }
+
+    pub fn blockchain_get_delegations(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Vec<([u8; BLOCK_ADDRESS_SIZE], u64)> {
+        self.blockchain.get_delegations(address)
+    }
+
+    pub fn blockchain_get_validator(&self, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Option<ValidatorAccount> {
+        self.blockchain.get_validator_by_address(address)
+    }
+
     pub fn blockchain_get_total_staked(&self) -> u64 {
         match self.blockchain.get_account(&VALIDATOR_ENABLE_RECIPIENT) {
             Some(account) => account.get_balance(),
@@ -255,6 +357,92 @@ impl Controller {
         }
     }
 
+    pub fn blockchain_get_loose_change_reward(&self) -> u64 {
+        self.blockchain.get_loose_change_reward()
+    }
+
+    pub fn blockchain_get_proposer_schedule(&self) -> Vec<([u8; BLOCK_ADDRESS_SIZE], u64)> {
+        // convert each scheduled proposer's public key to their address, their key may have been rotated
+        self.blockchain.get_proposer_schedule().iter().filter_map(|(proposer_pub_key, earliest_timestamp)| {
+            self.blockchain.get_address(proposer_pub_key).map(|proposer_address| (proposer_address, *earliest_timestamp))
+        }).collect()
+    }
+
+    pub fn blockchain_get_proposer_probability(&self) -> f64 {
+        self.blockchain.get_proposer_probability(&self.wallet_get_public_key())
+    }
+
+    pub fn blockchain_get_current_epoch(&self) -> Option<Epoch> {
+        self.blockchain.get_current_epoch()
+    }
+
+    pub fn blockchain_get_epoch(&self, number: u64) -> Option<Epoch> {
+        self.blockchain.get_epoch(number)
+    }
+
+    pub fn blockchain_get_epoch_validators(&self, epoch: &Epoch) -> Vec<([u8; BLOCK_ADDRESS_SIZE], u64)> {
+        // convert each of the epoch's validator public keys to their address, their key may have been rotated
+        epoch.get_validators().iter().filter_map(|(validator_pub_key, validator_weight)| {
+            self.blockchain.get_address(validator_pub_key).map(|validator_address| (validator_address, *validator_weight))
+        }).collect()
+    }
+
+    pub fn blockchain_is_deployment_active(&self, name: &str) -> bool {
+        self.blockchain.is_deployment_active(name)
+    }
+
+    pub fn blockchain_get_minimum_replacement_fee(&self, transaction: &Transaction) -> u64 {
+        self.blockchain.get_minimum_replacement_fee(transaction)
+    }
+
+    pub fn blockchain_get_base_fee(&self) -> f64 {
+        self.blockchain.get_base_fee() as f64 / LOWEST_DENOMINATION_PER_COIN
+    }
+
+    pub fn blockchain_get_total_burned(&self) -> u64 {
+        self.blockchain.get_total_burned()
+    }
+
+    pub fn blockchain_get_supply(&self, block_height: u64) -> Option<Supply> {
+        self.blockchain.get_supply(block_height)
+    }
+
+    pub fn blockchain_is_supply_conserved(&self) -> bool {
+        self.blockchain.is_supply_conserved()
+    }
+
+    pub fn blockchain_get_minimum_validator_enable_fee(&self) -> f64 {
+        self.blockchain.get_minimum_validator_enable_fee() as f64 / LOWEST_DENOMINATION_PER_COIN
+    }
+
+    pub fn blockchain_get_deployments(&self) -> Vec<(&'static str, u8, DeploymentState, u64, u64)> {
+        // the name, signal bit, state, height the state started at, and signals in the current window of every deployment
+        DEPLOYMENTS.iter().enumerate().filter_map(|(index, deployment)| {
+            let (state, since_height) = self.blockchain.get_deployment_state(index)?;
+            Some((deployment.get_name(), deployment.get_bit(), state, since_height, self.blockchain.get_deployment_signals(index)))
+        }).collect()
+    }
+
+    pub fn blockchain_get_finalized_height(&self) -> u64 {
+        self.blockchain.get_finalized_height()
+    }
+
+    pub fn blockchain_get_last_trusted_checkpoint_height(&self) -> u64 {
+        self.blockchain.get_last_trusted_checkpoint_height()
+    }
+
+    pub fn blockchain_is_block_final(&self, block_height: u64) -> bool {
+        self.blockchain.is_block_final(block_height)
+    }
+
+    pub fn blockchain_get_latest_checkpoint_height(&self) -> u64 {
+        self.blockchain.get_latest_checkpoint_height()
+    }
+
+    pub fn blockchain_get_attested_weight(&self, block_height: u64) -> (u64, u64) {
+        self.blockchain.get_attested_weight(block_height)
+    }
+
     pub fn blockchain_prune_mempool(&mut self) {
         self.blockchain.clear_mempool()
     }
@@ -267,16 +455,152 @@ impl Controller {
         self.blockchain.remove_transaction_mempool(transaction)
     }
 
-    pub fn transaction_create_a_b(&mut self, recipient: [u8; BLOCK_ADDRESS_SIZE], amount: u64, fee: u64) -> Option<Transaction> {
-        self.wallet.create_tx(amount, fee, recipient)
+    pub fn transaction_create_a_b(&mut self, recipient: [u8; BLOCK_ADDRESS_SIZE], amount: u64, fee: u64, tx_options: TxOptions) -> Option<Transaction> {
+        self.wallet.create_tx(amount, fee, recipient, tx_options, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_batch(&mut self, outputs: Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>, fee: u64) -> Option<Transaction> {
+        self.wallet.create_batch_tx(outputs, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_bump_fee(&self, transaction: &Transaction, fee: u64) -> Option<Transaction> {
+        self.wallet.create_replacement_tx(transaction, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_multisig(&self, recipient: [u8; BLOCK_ADDRESS_SIZE], amount: u64, fee: u64, threshold: u8, public_keys: &[[u8; COMPRESSED_PUBLIC_KEY_SIZE]]) -> Option<Transaction> {
+        // the multisig account's keys are kept sorted so the same key set always has the same address
+        let mut public_keys = public_keys.to_vec();
+        public_keys.sort();
+        let multisig = Multisig::new(threshold, public_keys, vec![]);
+
+        // the multisig account's nonce isn't tracked by the wallet, use the account nonce after any of its transactions already in the mempool
+        let address = multisig.get_address();
+        let account_nonce = self.blockchain.get_account(&address).map(|account| account.get_nonce()).unwrap_or(0);
+        let mempool_transactions = self.blockchain.get_mempool_clone().get(&address).map(|transactions| transactions.len() as u64).unwrap_or(0);
+
+        self.wallet.create_multisig_tx(amount, fee, recipient, account_nonce + mempool_transactions, multisig, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_sign_multisig(&self, transaction: &Transaction) -> Option<Transaction> {
+        self.wallet.sign_multisig_tx(transaction, self.blockchain.get_signed_chain_id())
+    }
+
+    pub fn wallet_generate_multisig_address(&self, threshold: u8, public_keys: &[[u8; COMPRESSED_PUBLIC_KEY_SIZE]]) -> [u8; BLOCK_ADDRESS_SIZE] {
+        Wallet::generate_multisig_address(threshold, public_keys)
     }
 
     pub fn transaction_create_validator_enable(&mut self, amount: u64, fee: u64) -> Option<Transaction> {
-        self.wallet.create_validator_enable_tx(amount, fee)
+        self.wallet.create_validator_enable_tx(amount, fee, self.blockchain.get_tx_format())
     }
 
     pub fn transaction_create_validator_revoke(&mut self, amount: u64, fee: u64) -> Option<Transaction> {
-        self.wallet.create_validator_revoke_tx(amount, fee)
+        self.wallet.create_validator_revoke_tx(amount, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_validator_stake_increase(&mut self, amount: u64, fee: u64) -> Option<Transaction> {
+        self.wallet.create_validator_stake_increase_tx(amount, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_validator_stake_withdraw(&mut self, amount: u64, fee: u64) -> Option<Transaction> {
+        self.wallet.create_validator_stake_withdraw_tx(amount, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_delegate(&mut self, validator_address: [u8; BLOCK_ADDRESS_SIZE], amount: u64, fee: u64) -> Option<Transaction> {
+        self.wallet.create_delegate_tx(validator_address, amount, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_undelegate(&mut self, validator_address: [u8; BLOCK_ADDRESS_SIZE], amount: u64, fee: u64) -> Option<Transaction> {
+        self.wallet.create_undelegate_tx(validator_address, amount, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_validator_commission(&mut self, commission: u16, fee: u64) -> Option<Transaction> {
+        self.wallet.create_validator_commission_tx(commission, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_htlc_lock(&mut self, recipient: [u8; BLOCK_ADDRESS_SIZE], amount: u64, hash_lock: [u8; 32], refund_height: u64, fee: u64) -> Option<Transaction> {
+        self.wallet.create_htlc_lock_tx(recipient, amount, hash_lock, refund_height, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_htlc_claim(&mut self, lock_id: [u8; 32], preimage: [u8; 32], fee: u64) -> Option<Transaction> {
+        self.wallet.create_htlc_claim_tx(lock_id, preimage, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_htlc_refund(&mut self, lock_id: [u8; 32], fee: u64) -> Option<Transaction> {
+        self.wallet.create_htlc_refund_tx(lock_id, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_channel_open(&mut self, payee: [u8; BLOCK_ADDRESS_SIZE], deposit: u64, fee: u64) -> Option<Transaction> {
+        self.wallet.create_channel_open_tx(payee, deposit, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_channel_close(&mut self, channel_update: ChannelUpdate, fee: u64) -> Option<Transaction> {
+        self.wallet.create_channel_close_tx(channel_update, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn wallet_create_channel_update(&self, channel_id: [u8; 32], paid: u64) -> Option<ChannelUpdate> {
+        self.wallet.create_channel_update(channel_id, paid, self.blockchain.get_tx_format())
+    }
+
+    pub fn blockchain_verify_channel_update(&self, channel_update: &ChannelUpdate) -> bool {
+        verification_engine::verify_channel_update(channel_update, &self.blockchain)
+    }
+
+    pub fn transaction_create_key_rotation(&mut self, new_public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE], fee: u64) -> Option<Transaction> {
+        self.wallet.create_key_rotation_tx(new_public_key, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn wallet_generate_rotation_key(&self) -> (String, [u8; COMPRESSED_PUBLIC_KEY_SIZE]) {
+        self.wallet.generate_rotation_key()
+    }
+
+    pub fn wallet_import_key(&mut self, wif_private_key_string: &str, account_address: [u8; BLOCK_ADDRESS_SIZE]) -> bool {
+        // obtain the public key of the private key being imported
+        let public_key = match self.wallet.get_wif_public_key(wif_private_key_string) {
+            Some(public_key) => public_key,
+            None => {
+                println!("Invalid private key, please check for typos");
+                return false
+            }
+        };
+
+        // only import a key that controls the account in the blockchain's view, the key rotation transaction must have been added to a block
+        if self.blockchain.get_address(&public_key) != Some(account_address) {
+            println!("The private key doesn't control the account, make sure the key rotation transaction has been added to a block");
+            return false
+        }
+
+        if self.wallet.import_key(wif_private_key_string, account_address).is_none() {
+            return false
+        }
+
+        // continue from the account nonce in the blockchain's view
+        let nonce = match self.blockchain.get_account(&account_address) {
+            Some(account) => account.get_nonce(),
+            None => 0
+        };
+        self.wallet.set_nonce(nonce);
+
+        true
+    }
+
+    pub fn blockchain_get_sender_address(&self, transaction: &Transaction) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        self.blockchain.get_sender_address(transaction)
+    }
+
+    pub fn transaction_create_name_register(&mut self, name: [u8; MAX_NAME_SIZE], fee: u64) -> Option<Transaction> {
+        self.wallet.create_name_register_tx(name, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_name_renew(&mut self, name: [u8; MAX_NAME_SIZE], fee: u64) -> Option<Transaction> {
+        self.wallet.create_name_renew_tx(name, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn transaction_create_name_transfer(&mut self, name: [u8; MAX_NAME_SIZE], recipient: [u8; BLOCK_ADDRESS_SIZE], fee: u64) -> Option<Transaction> {
+        self.wallet.create_name_transfer_tx(name, recipient, fee, self.blockchain.get_tx_format())
+    }
+
+    pub fn wallet_generate_htlc_preimage(&self) -> [u8; 32] {
+        Wallet::generate_htlc_preimage()
     }
 
     pub fn network_get_peers(&self) -> Vec<Peer> {
@@ -303,6 +627,30 @@ impl Controller {
         self.network.broadcast_transaction(&transaction).await
     }
 
+    pub async fn network_broadcast_attestation(&mut self, attestation: &Attestation) -> Option<Vec<Peer>> {
+        self.network.broadcast_attestation(attestation).await
+    }
+
+    pub async fn network_get_proposer_schedule(&mut self, peer: &Peer) -> Option<(Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>, f64)> {
+        let public_key = self.wallet_get_public_key();
+        let (schedule, probability) = self.network.get_proposer_schedule(peer, &public_key).await?;
+
+        // convert each scheduled proposer's public key to their address, their key may have been rotated
+        let schedule = schedule.iter().filter_map(|(proposer_pub_key, earliest_timestamp)| {
+            self.blockchain.get_address(proposer_pub_key).map(|proposer_address| (proposer_address, *earliest_timestamp))
+        }).collect();
+
+        Some((schedule, probability))
+    }
+
+    pub async fn network_resolve_name(&mut self, peer: &Peer, name: &str) -> Option<Option<[u8; BLOCK_ADDRESS_SIZE]>> {
+        self.network.resolve_name(peer, name).await
+    }
+
+    pub async fn network_get_names(&mut self, peer: &Peer, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Option<Vec<String>> {
+        self.network.get_names(peer, address).await
+    }
+
     pub fn about_wallet_config(&self) -> WalletConfig {
         self.config.get_wallet_config()
     }
@@ -315,6 +663,10 @@ impl Controller {
         self.config.get_network_config()
     }
 
+    pub fn about_blockchain_config(&self) -> BlockchainConfig {
+        self.config.get_blockchain_config()
+    }
+
     pub fn check_address_checksum(&self, address: [u8; BLOCK_ADDRESS_SIZE]) -> bool {
         self.wallet.check_address_checksum(address)
     }
@@ -328,7 +680,9 @@ impl Controller {
         let mut tx_vec: Vec<Transaction> = vec![];
 
         // create coinbase transaction for this block
-        match self.wallet.create_coinbase_tx(verification_engine::get_block_subsidy(block_height + 1), self.wallet.get_address()) {
+        // the block reward is the block subsidy plus the loose change recycled into this block
+        let block_reward = verification_engine::get_block_subsidy(block_height + 1) + self.blockchain.get_loose_change_reward();
+        match self.wallet.create_coinbase_tx(block_reward, self.wallet.get_address()) {
             Some(coinbase_tx) => tx_vec.push(coinbase_tx),
             // if coinbase_tx cannot be created either do not propose the block or propose it without a coinbase transaction depending on config
             None =>  {
@@ -345,34 +699,62 @@ impl Controller {
         // get a list of the accounts on the blockchain, used for nonce checking later
         let mut blockchain_accounts = self.blockchain.get_accounts();
 
+        // transactions must pay at least the base fee to be added to the block
+        let base_fee = self.blockchain.get_base_fee();
+
+        // get the current timestamp
+        let timestamp = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(timestamp) => timestamp.as_secs(),
+            Err(_) => return None,
+        };
+
         // get a reference to the mempool
         let mempool = self.blockchain.get_mempool();
 
         // iterate through the mempool while there are transactions left in it, or the tx_vec is at MAX_TRANSACTION-PER_BLOCK
-        while !mempool.is_empty() && tx_vec.len() < *MAX_TRANSACTIONS_PER_BLOCK {
+        // number of validator enable transactions added to the block, there can be at most MAX_VALIDATOR_ENABLES_PER_BLOCK
+        let mut validator_enables = 0;
+
+        // block space used so far, a batch transaction counts once for each of its outputs
+        let mut block_weight = tx_vec.len() as u64;
+
+        while !mempool.is_empty() && block_weight < *MAX_TRANSACTIONS_PER_BLOCK as u64 {
             // hold the address and max fee found from the first transaction of each account's transaction vector in the mempool hashmap
-            let mut max_transaction_fee_sender: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = [0x00; COMPRESSED_PUBLIC_KEY_SIZE];
+            let mut max_transaction_fee_sender: [u8; BLOCK_ADDRESS_SIZE] = [0x00; BLOCK_ADDRESS_SIZE];
             let mut max_transaction_fee: Option<u64> = None;
 
             // iterate through each account that has a transaction in the mempool, and check its first (earliest nonce) transaction and check if its fee is higher than the max already found fee
             for (sender, transactions) in mempool.clone() {
+                // skip transactions that don't pay the base fee, they can be added to a later block if the base fee decreases
+                if transactions[0].fee < base_fee * transactions[0].get_weight() {
+                    continue
+                }
+
+                // skip transactions that don't fit in the rest of the block
+                if block_weight + transactions[0].get_weight() > *MAX_TRANSACTIONS_PER_BLOCK as u64 {
+                    continue
+                }
+
+                // transactions are compared by their fee per unit of weight, so a batch transaction competes with the single transactions it replaces
+                let transaction_fee = transactions[0].fee / transactions[0].get_weight();
+
+                // skip transactions outside of their validity window, expired transactions are removed from the mempool once the block is added
+                if !transactions[0].is_valid_at(block_height + 1, timestamp) {
+                    continue
+                }
+
+                // skip validator enable transactions once the block is full of them, they can be added to a later block
+                if validator_enables >= *MAX_VALIDATOR_ENABLES_PER_BLOCK && transactions[0].recipient == *VALIDATOR_ENABLE_RECIPIENT {
+                    continue
+                }
+
                 match max_transaction_fee {
                     // if there already is a max_transaction_fee compare the current transaction's fee to the max transaction fee
                     Some(max_fee) => {
                         // if the current transaction's fee is higher than the max transaction fee then confirm the nonce is correct for this transaction
-                        if transactions[0].fee > max_fee {
-                            // get the account public key
-                            let account_pub_key = match PublicKey::from_sec1_bytes(&sender) {
-                                Ok(account_pub_key) => account_pub_key,
-                                // if an invalid public key is received then the transaction is invalid
-                                Err(_) => continue
-                            };
-
-                            // get the address for the account public key
-                            let account_address = Wallet::generate_address(&account_pub_key, true);
-
+                        if transaction_fee > max_fee {
                             // obtain the account nonce in the blockchains view
-                            let tx_account_nonce = match blockchain_accounts.get(&account_address) {
+                            let tx_account_nonce = match blockchain_accounts.get(&sender) {
                                 Some(tx_account) => {
                                     tx_account.get_nonce()
                                 }
@@ -385,7 +767,7 @@ impl Controller {
                             if tx_account_nonce == transactions[0].nonce {
                                 // if the transaction nonce matches the account nonce on the blockchain then mark this transaction as the new max fee
                                 max_transaction_fee_sender = sender;
-                                max_transaction_fee = Some(transactions[0].fee);
+                                max_transaction_fee = Some(transaction_fee);
                             } 
 
                             if transactions[0].nonce < tx_account_nonce {
@@ -405,18 +787,8 @@ impl Controller {
                     },
                     // if there is no max transaction fee yet then add the first transaction that has a valid nonce
                     None => {
-                        // get the account public key
-                        let account_pub_key = match PublicKey::from_sec1_bytes(&sender) {
-                            Ok(account_pub_key) => account_pub_key,
-                            // if an invalid public key is received then the transaction is invalid
-                            Err(_) => continue
-                        };
-
-                        // get the address for the account public key
-                        let account_address = Wallet::generate_address(&account_pub_key, true);
-
                         // obtain the account nonce in the blockchains view
-                        let tx_account_nonce = match blockchain_accounts.get(&account_address) {
+                        let tx_account_nonce = match blockchain_accounts.get(&sender) {
                             Some(tx_account) => {
                                 tx_account.get_nonce()
                             }
@@ -429,7 +801,7 @@ impl Controller {
                         // if the transaction nonce matches the account nonce on the blockchain then mark this transaction as the new max fee
                         if tx_account_nonce == transactions[0].nonce {
                             max_transaction_fee_sender = sender;
-                            max_transaction_fee = Some(transactions[0].fee);
+                            max_transaction_fee = Some(transaction_fee);
 
                             continue;
                         }
@@ -437,14 +809,14 @@ impl Controller {
                         if transactions[0].nonce < tx_account_nonce {
                             // if the user has sent a transaction with a nonce less than their account nonce, remove it from the mempool because this will never be a valid transaction
                             // get the sender's tx vec
-                            let sender_tx_vec = mempool.get_mut(&transactions[0].sender).unwrap();
+                            let sender_tx_vec = mempool.get_mut(&sender).unwrap();
 
                             // remove the transaction from the mempool
                             sender_tx_vec.remove(0);
 
                             // if the sender has no more transactions remove their entry from the mempool hash map
                             if sender_tx_vec.len() == 0 {
-                                mempool.remove(&transactions[0].sender);
+                                mempool.remove(&sender);
                             }
                         }
                     }
@@ -452,11 +824,15 @@ impl Controller {
             }
 
             // if the max fee transaction was updated then add it to the tx vec and remove it from the mempool
-            if max_transaction_fee_sender != [0x00; COMPRESSED_PUBLIC_KEY_SIZE] {
+            if max_transaction_fee_sender != [0x00; BLOCK_ADDRESS_SIZE] {
                 // get the sender's tx vec with the highest transaction fee for this iteration
                 let sender_tx_vec = mempool.get_mut(&max_transaction_fee_sender).unwrap();
 
                 // push their transaction into the tx_vec for the block
+                if sender_tx_vec[0].recipient == *VALIDATOR_ENABLE_RECIPIENT {
+                    validator_enables += 1;
+                }
+                block_weight += sender_tx_vec[0].get_weight();
                 tx_vec.push(sender_tx_vec[0].clone());
 
                 // remove the transaction from the mempool
@@ -468,36 +844,58 @@ impl Controller {
                 }
 
                 // after a transaction is added to the block update the account nonce (in the blockchain account CLONE NOT the actual blockchain) so that multiple transactions per account can be added per block
-                // get the account public key
-                let account_pub_key = match PublicKey::from_sec1_bytes(&max_transaction_fee_sender) {
-                    Ok(account_pub_key) => account_pub_key,
-                    // if an invalid public key is received then the transaction is invalid
-                    Err(_) => continue
-                };
-
-                // get the address for the account public key
-                let account_address = Wallet::generate_address(&account_pub_key, true);
-
-                blockchain_accounts.get_mut(&account_address).unwrap().increase_nonce();
+                // an htlc claim or a payment channel close by its payee can be sent from an address that doesn't have an account yet
+                blockchain_accounts.entry(max_transaction_fee_sender).or_insert_with(|| Account::new(max_transaction_fee_sender)).increase_nonce();
             } else {
                 // if the max fee transaction was never updated then there are no valid transactions in the mempool
                 break;
             }
         }
 
-        // get the current timestamp
-        let timestamp = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-            Ok(timestamp) => timestamp.as_secs(),
-            Err(_) => return None,
-        };
+        // prove the vrf output over the current randomness beacon, this is mixed into the beacon that chooses the next proposer
+        let vrf_proof = self.wallet.create_vrf_proof(&self.blockchain.get_randomness_beacon())?;
+
+        // the block version signals for every deployment validators are currently signaling for
+        let version = self.blockchain.get_next_block_version();
 
         // create the block signature
-        let block_sig = match self.wallet.create_block_sig(*BLOCK_VERSION, prev_hash, timestamp, &tx_vec) {
+        let block_sig = match self.wallet.create_block_sig(version, prev_hash, timestamp, vrf_proof, &tx_vec, self.blockchain.get_signed_chain_id()) {
             Some(block_sig) => block_sig,
             None => return None
         };
         
-        Some(self.validator.create_block(&mut tx_vec, prev_hash, timestamp, block_sig))
+        Some(self.validator.create_block(version, &mut tx_vec, prev_hash, timestamp, vrf_proof, block_sig))
+    }
+
+    pub async fn validator_attest_checkpoint(&mut self) -> bool {
+        // attest to the most recent checkpoint block, the genesis block is always final so it is never attested to
+        let checkpoint_height = self.blockchain.get_latest_checkpoint_height();
+        if checkpoint_height == 0 {
+            println!("No checkpoint block to attest to yet, the first checkpoint is at block height {}", *CHECKPOINT_INTERVAL);
+            println!();
+            return false
+        }
+
+        let checkpoint_hash = match self.blockchain.get_block(checkpoint_height) {
+            Some(checkpoint_block) => checkpoint_block.serialize_hash_block_header().try_into().unwrap(),
+            None => return false
+        };
+
+        let attestation = match self.wallet.create_attestation(checkpoint_height, checkpoint_hash) {
+            Some(attestation) => attestation,
+            None => return false
+        };
+
+        // add the attestation locally, this fails if the wallet isn't a current validator or already attested
+        if !self.blockchain.add_attestation(&attestation) {
+            println!("Failed attesting to checkpoint block at height {}, you may not be a validator, may have already attested, or the checkpoint may already be final", checkpoint_height);
+            println!();
+            return false
+        }
+
+        self.network_broadcast_attestation(&attestation).await;
+
+        true
     }
 
     async fn sync_blockchain(&mut self) -> bool {
@@ -606,6 +1004,10 @@ impl Controller {
             return false;
         }
 
+        // build the peer's blockchain separately so it can be compared to the current blockchain before replacing it
+        let mut blockchain = Blockchain::new(self.config.get_blockchain_config());
+        blockchain.add_genesis_block();
+
         // fetch all blocks from block height 1 to tallest_chain blocks from the peer, verify them, and add to blockchain 
         for i in 1..=tallest_chain_height {
              // obtain the block from the peer
@@ -619,7 +1021,7 @@ impl Controller {
             };
 
             // attempt to add block to the blockchain
-            let (result, blockchain) = self.blockchain.clone().add_block(&block);
+            let (result, new_blockchain) = blockchain.clone().add_block(&block);
             if !result {
                 println!("Invalid block at height {} received from peer: {}:{}", i, tallest_peer.get_ip(), tallest_peer.get_port());
                 println!();
@@ -627,9 +1029,32 @@ impl Controller {
             }
             
             // set the new blockchain as the blockchain with the added block
-            self.blockchain = blockchain;
+            blockchain = new_blockchain;
+
+            // obtain the attestations for checkpoint blocks while the validator set is the one that attested to them, invalid attestations are ignored
+            if i.is_multiple_of(*CHECKPOINT_INTERVAL) {
+                for attestation in self.network.get_attestations(&tallest_peer, i).await.unwrap_or_default().iter() {
+                    blockchain.add_attestation(attestation);
+                }
+            }
+        }
+
+        // the peer's blockchain must reach the last trusted checkpoint, the blocks below it were only checked against that checkpoint
+        if blockchain.get_block_height() < blockchain.get_last_trusted_checkpoint_height() {
+            println!("Blockchain from peer: {}:{} doesn't reach the trusted checkpoint at height {}, refusing to synchronize", tallest_peer.get_ip(), tallest_peer.get_port(), blockchain.get_last_trusted_checkpoint_height());
+            println!();
+            return false;
+        }
+
+        // never replace final blocks, even if the peer has a taller chain
+        if !self.blockchain.is_reorg_allowed(&blockchain) {
+            println!("Blockchain from peer: {}:{} conflicts with final block at height {}, refusing to synchronize", tallest_peer.get_ip(), tallest_peer.get_port(), self.blockchain.get_finalized_height());
+            println!();
+            return false;
         }
 
+        self.blockchain = blockchain;
+
         true
     }
 
@@ -643,7 +1068,12 @@ impl Controller {
         let (result, blockchain) = self.blockchain.clone().add_block(&block);
         
         if result {
-            self.blockchain = blockchain
+            self.blockchain = blockchain;
+
+            // attest to checkpoint blocks as soon as they are added
+            if self.blockchain.get_block_height().is_multiple_of(*CHECKPOINT_INTERVAL) {
+                self.validator_attest_checkpoint().await;
+            }
         } else {
             println!("Failed to add block to blockchain");
             println!("Blokc: {:?}", block);
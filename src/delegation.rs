@@ -0,0 +1,35 @@
+use crate::constants::BLOCK_ADDRESS_SIZE;
+
+// Stake delegated to a validator by another account
+#[derive(Debug, Clone)]
+pub struct Delegation {
+    // address of the account that delegated the stake
+    delegator: [u8; BLOCK_ADDRESS_SIZE],
+    // amount of stake delegated
+    amount: u64,
+}
+
+impl Delegation {
+    pub fn new(delegator: [u8; BLOCK_ADDRESS_SIZE], amount: u64) -> Self {
+        Self {
+            delegator,
+            amount,
+        }
+    }
+
+    pub fn get_delegator(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        self.delegator
+    }
+
+    pub fn increase_amount(&mut self, amount: u64) {
+        self.amount += amount
+    }
+
+    pub fn decrease_amount(&mut self, amount: u64) {
+        self.amount -= amount
+    }
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount
+    }
+}
\ No newline at end of file
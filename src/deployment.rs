@@ -0,0 +1,77 @@
+// State of a deployment, deployments only change state at the end of a DEPLOYMENT_WINDOW
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeploymentState {
+    // the deployment's start height hasn't been reached
+    Defined,
+    // validators are signaling for the deployment
+    Started,
+    // enough validators signaled for the deployment, it activates at the end of the next window
+    LockedIn,
+    // the deployment's rules are enforced
+    Active,
+    // the deployment wasn't locked in before its timeout height
+    Failed,
+}
+
+// A protocol upgrade validators signal readiness for through a bit of the block header version
+#[derive(Debug)]
+pub struct Deployment {
+    // name of the deployment
+    name: &'static str,
+    // bit of the block header version used to signal for the deployment, bits 8 to 31 (the low 8 bits are the block format version)
+    bit: u8,
+    // block height from which validators can signal for the deployment
+    start_height: u64,
+    // block height at which the deployment fails if it hasn't been locked in
+    timeout_height: u64,
+    // block format version introduced by the deployment, 0 if the deployment doesn't add a block format version
+    block_version: u32,
+    // transaction version introduced by the deployment, 0 if the deployment doesn't add a transaction version
+    transaction_version: u8,
+}
+
+impl Deployment {
+    pub const fn new(name: &'static str, bit: u8, start_height: u64, timeout_height: u64, block_version: u32, transaction_version: u8) -> Self {
+        Self {
+            name,
+            bit,
+            start_height,
+            timeout_height,
+            block_version,
+            transaction_version,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_bit(&self) -> u8 {
+        self.bit
+    }
+
+    pub fn get_start_height(&self) -> u64 {
+        self.start_height
+    }
+
+    pub fn get_timeout_height(&self) -> u64 {
+        self.timeout_height
+    }
+
+    pub fn get_block_version(&self) -> u32 {
+        self.block_version
+    }
+
+    pub fn get_transaction_version(&self) -> u8 {
+        self.transaction_version
+    }
+
+    pub fn get_signal(&self) -> u32 {
+        // the block header version bits that signal for this deployment
+        1 << self.bit
+    }
+
+    pub fn is_signaled(&self, version: u32) -> bool {
+        version & self.get_signal() != 0
+    }
+}
\ No newline at end of file
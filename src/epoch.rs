@@ -0,0 +1,46 @@
+use crate::constants::{COMPRESSED_PUBLIC_KEY_SIZE, EPOCH_LENGTH};
+
+// The validator set and weights used to choose block proposers for EPOCH_LENGTH blocks
+#[derive(Debug, Clone)]
+pub struct Epoch {
+    // epoch number, epoch n is used for block heights n * EPOCH_LENGTH + 1 through (n + 1) * EPOCH_LENGTH
+    number: u64,
+    // public key and weight (stake + delegated stake) of each validator at the start of the epoch
+    validators: Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)>,
+}
+
+impl Epoch {
+    pub fn new(number: u64, validators: Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)>) -> Self {
+        Self {
+            number,
+            validators,
+        }
+    }
+
+    pub fn get_number(&self) -> u64 {
+        self.number
+    }
+
+    pub fn get_start_height(&self) -> u64 {
+        self.number * *EPOCH_LENGTH + 1
+    }
+
+    pub fn get_end_height(&self) -> u64 {
+        (self.number + 1) * *EPOCH_LENGTH
+    }
+
+    pub fn get_validators(&self) -> Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)> {
+        self.validators.clone()
+    }
+
+    pub fn replace_public_key(&mut self, old_public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE], new_public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE]) {
+        // a validator that rotated its key keeps its place and weight in the epoch
+        for (public_key, _) in self.validators.iter_mut().filter(|(public_key, _)| public_key == old_public_key) {
+            *public_key = new_public_key;
+        }
+    }
+
+    pub fn get_total_weight(&self) -> u64 {
+        self.validators.iter().map(|(_, weight)| weight).sum()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use sha2::{Sha256, Digest};
+
+use crate::constants::BLOCK_ADDRESS_SIZE;
+
+// Funds locked by a hash time-locked transfer, claimable by the recipient with the preimage of the hash lock until the refund height
+#[derive(Debug, Clone)]
+pub struct Htlc {
+    // address of the account that locked the funds, it can take them back from the refund height
+    sender: [u8; BLOCK_ADDRESS_SIZE],
+    // address of the account that can claim the funds by revealing the preimage
+    recipient: [u8; BLOCK_ADDRESS_SIZE],
+    // amount of funds locked
+    amount: u64,
+    // sha256 hash of the secret preimage
+    hash_lock: [u8; 32],
+    // block height from which the funds can no longer be claimed and the sender can refund them
+    refund_height: u64,
+}
+
+impl Htlc {
+    pub fn new(sender: [u8; BLOCK_ADDRESS_SIZE], recipient: [u8; BLOCK_ADDRESS_SIZE], amount: u64, hash_lock: [u8; 32], refund_height: u64) -> Self {
+        Self {
+            sender,
+            recipient,
+            amount,
+            hash_lock,
+            refund_height,
+        }
+    }
+
+    pub fn get_sender(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        self.sender
+    }
+
+    pub fn get_recipient(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        self.recipient
+    }
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount
+    }
+
+    pub fn get_hash_lock(&self) -> [u8; 32] {
+        self.hash_lock
+    }
+
+    pub fn get_refund_height(&self) -> u64 {
+        self.refund_height
+    }
+
+    pub fn is_unlocked_by(&self, preimage: &[u8; 32]) -> bool {
+        Self::hash_preimage(preimage) == self.hash_lock
+    }
+
+    pub fn hash_preimage(preimage: &[u8; 32]) -> [u8; 32] {
+        // the hash lock is sha256(preimage), the same hash lock can be used on another chain for an atomic swap
+        let mut sha256_hasher = Sha256::new();
+        sha256_hasher.update(preimage);
+        sha256_hasher.finalize().into()
+    }
+}
@@ -1,23 +1,38 @@
 mod account;
+mod attestation;
 mod block;
 mod blockchain;
+mod channel_update;
 mod config;
 mod constants;
 mod controller;
+mod delegation;
+mod deployment;
+mod epoch;
+mod htlc;
+mod multisig;
+mod name_record;
 mod network;
+mod payment_channel;
+mod signature;
+mod supply;
 mod transaction;
+mod tx_format;
+mod tx_options;
+mod unbonding_entry;
 mod wallet;
 mod validator;
 mod validator_account;
 mod verification_engine;
+mod vrf;
 mod util;
 
 use std::io::{self, Write};
 
-use crate::{controller::Controller, network::Peer};
-use crate::util::read_string;
+use crate::{channel_update::ChannelUpdate, controller::Controller, htlc::Htlc, name_record::NameRecord, network::Peer, payment_channel::PaymentChannel, transaction::{Transaction, TxLock}, tx_options::TxOptions};
+use crate::util::{decode_hex, encode_hex, read_string};
 
-use constants::{BLOCK_ADDRESS_SIZE, LOWEST_DENOMINATION_PER_COIN, NODE_VERSION};
+use constants::{BLOCK_ADDRESS_SIZE, CHANNEL_DISPUTE_PERIOD, COMPRESSED_PUBLIC_KEY_SIZE, DEPLOYMENT_THRESHOLD, DEPLOYMENT_WINDOW, LOWEST_DENOMINATION_PER_COIN, MAX_BATCH_OUTPUTS, MAX_MEMO_SIZE, MAX_MULTISIG_PUBLIC_KEYS, MAX_NAME_SIZE, MIN_NAME_SIZE, NAME_REGISTRATION_FEE, NAME_REGISTRATION_PERIOD, NODE_VERSION, UNBONDING_PERIOD};
 
 // ToDo: refactor where async-ness should happen
 #[tokio::main]
@@ -82,7 +97,7 @@ async fn output_options(controller: &mut Controller) {
                 perform_wallet_options(controller);
             },
             "2" | "2." | "blockchain" => {
-                perform_blockhain_options(controller);
+                perform_blockhain_options(controller).await;
             },
             "3" | "3." | "transaction" => {
                 perform_transaction_options(controller).await;
@@ -109,7 +124,7 @@ async fn output_options(controller: &mut Controller) {
 }
 
 fn perform_wallet_options(controller: &mut Controller) {
-    let wallet_options = vec!["Wallet Options:", "View Overview", "View address", "View balance", "View nonce", "View private key", "Set nonce", "Back"];
+    let wallet_options = vec!["Wallet Options:", "View Overview", "View address", "View balance", "View nonce", "View private key", "View unbonding", "View delegations", "Set nonce", "Import rotated key", "Back"];
 
     loop {
         // present wallet options to user
@@ -149,7 +164,23 @@ fn perform_wallet_options(controller: &mut Controller) {
                 
                 println!();
             },
-            "6" | "6." | "set nonce" => {
+            "6" | "6." | "view unbonding" | "unbonding" => {
+                // display the total amount unbonding and each entry in the unbonding queue with the height it will be released at
+                println!("Unbonding: {:.8} BLO", controller.wallet_get_unbonding());
+                for entry in controller.blockchain_get_unbonding_queue(&controller.wallet_get_address()) {
+                    println!("\t{:.8} BLO released at block height {}", entry.get_amount() as f64 / LOWEST_DENOMINATION_PER_COIN, entry.get_release_height());
+                }
+                println!();
+            },
+            "7" | "7." | "view delegations" | "delegations" => {
+                // display the total amount delegated and the amount delegated to each validator
+                println!("Delegated: {:.8} BLO", controller.wallet_get_delegated());
+                for (validator_address, amount) in controller.blockchain_get_delegations(&controller.wallet_get_address()) {
+                    println!("\t{:.8} BLO delegated to {}", amount as f64 / LOWEST_DENOMINATION_PER_COIN, String::from_utf8_lossy(&validator_address));
+                }
+                println!();
+            },
+            "8" | "8." | "set nonce" => {
                 loop {
                     // display the current wallet nonce and prompt the user for the new nonce
                     println!("Current nonce: {}", controller.wallet_get_nonce());
@@ -170,7 +201,45 @@ fn perform_wallet_options(controller: &mut Controller) {
                     break;
                 }
             },
-            "7" | "7." | "back" => {
+            "9" | "9." | "import rotated key" | "import key" | "import" => {
+                // prompt the user for the account the key has been rotated to, usually the wallet's own account
+                println!("Current address: {}", controller.wallet_get_address_string());
+                println!("Enter the address of the account the key has been rotated to (case sensitive), leave blank for the current address or \"exit\":");
+                let address_string = read_string();
+                println!();
+
+                if address_string.to_lowercase() == "exit" {
+                    continue;
+                }
+
+                let account_address: [u8; BLOCK_ADDRESS_SIZE] = if address_string.is_empty() {
+                    controller.wallet_get_address()
+                } else {
+                    match address_string.as_bytes().try_into() {
+                        Ok(account_address) if controller.check_address_checksum(account_address) => account_address,
+                        _ => {
+                            println!("Invalid address, please check for typos");
+                            println!();
+                            continue;
+                        }
+                    }
+                };
+
+                // prompt the user for the private key the account has been rotated to
+                println!("Enter the private key (WIF) the account has been rotated to or \"exit\", it replaces the private key in the wallet file:");
+                let wif_private_key = read_string();
+                println!();
+
+                if wif_private_key.to_lowercase() == "exit" {
+                    continue;
+                }
+
+                if controller.wallet_import_key(&wif_private_key, account_address) {
+                    println!("Imported key for {}", String::from_utf8_lossy(&account_address));
+                }
+                println!();
+            },
+            "10" | "10." | "back" => {
                 break;
             },
             _ => {}
@@ -178,8 +247,8 @@ fn perform_wallet_options(controller: &mut Controller) {
     }
 }
 
-fn perform_blockhain_options(controller: &mut Controller) {
-    let blockchain_options = vec!["Blockchain Options:", "View Overview", "View block height", "View block", "View transaction", "View address", "View mempool", "View validators", "View total staked", "View total loose change", "Prune mempool", "Back"];
+async fn perform_blockhain_options(controller: &mut Controller) {
+    let blockchain_options = vec!["Blockchain Options:", "View Overview", "View block height", "View block", "View transaction", "View address", "View mempool", "View validators", "View total staked", "View total loose change", "View base fee", "View supply", "View proposer schedule", "View epoch", "View finality", "Attest checkpoint", "View deployments", "Prune mempool", "Back"];
 
     loop {
         // present blockchain options to user
@@ -220,36 +289,60 @@ fn perform_blockhain_options(controller: &mut Controller) {
                     }
                     
                     println!();
-                    println!("{:X?}", controller.blockchain_get_block(block_height).unwrap());
+                    let block = controller.blockchain_get_block(block_height).unwrap();
+                    println!("{:X?}", block);
+                    // the header hash is what trusted checkpoints and attestations refer to the block by
+                    println!("Header hash: {}", encode_hex(&block.serialize_hash_block_header()));
+                    println!("Final: {}", controller.blockchain_is_block_final(block_height));
                     println!();
 
                     break;
                 }
             },
             "4" | "4." | "view transaction" | "transaction" => {
-                // ToDo:
                 loop {
-                    println!("Not yet implemented");
+                    // prompt the user for the id of the transaction theyd like to view
+                    println!("Enter the transaction ID in hex or \"exit\":");
+                    let txid_string = read_string();
                     println!();
-                    // println!("Enter the transaction hash:");
-                    // let tx_hash = read_string().to_lowercase();
 
-                    // if !validate_tx_hash(tx_hash) {
-                    //     println!("Invalid transaction hash: {}", tx_hash);
-                    //     continue;
-                    // }
-                    
-                    // println!();
-                    // println!("{:X?}", controller.blockchain_get_transaction(tx_hash).unwrap());
-                    // println!();
+                    // exit if they entered exit
+                    if txid_string.to_lowercase() == "exit" {
+                        break;
+                    }
+
+                    // the transaction id is sha256(serialized transaction without its signatures)
+                    let txid = match decode_hex(&txid_string) {
+                        Some(txid) if txid.len() == 32 => txid,
+                        _ => {
+                            println!("Invalid transaction ID: {}", txid_string);
+                            println!();
+                            continue;
+                        }
+                    };
+
+                    match controller.blockchain_get_transaction(&txid) {
+                        Some((transaction, block_height)) => {
+                            println!("{:X?}", transaction);
+                            if !transaction.memo.is_empty() {
+                                println!("Memo: {}", String::from_utf8_lossy(&transaction.memo));
+                            }
+                            match block_height {
+                                Some(block_height) => println!("Included in block at height {}", block_height),
+                                None => println!("Waiting in the mempool")
+                            }
+                        },
+                        None => println!("Transaction not found on the blockchain or in the mempool")
+                    }
 
+                    println!();
                     break;
                 }
             },
             "5" | "5." | "view address" | "address" => {
                 loop {
                     // prompt the user for the address theyd like to view
-                    println!("Enter the address (case sensitive) or \"exit\":");
+                    println!("Enter the address (case sensitive) or a registered name or \"exit\":");
                     let address_string = read_string();
                     println!();
 
@@ -259,6 +352,8 @@ fn perform_blockhain_options(controller: &mut Controller) {
                         break;
                     }
 
+                    let address_string = resolve_name(controller, address_string).await;
+
                     // attempt to convert the address string to a vector
                     let address_arr: [u8; 39] = match address_string.as_bytes().try_into() {
                         Ok(address ) => address,
@@ -282,6 +377,23 @@ fn perform_blockhain_options(controller: &mut Controller) {
                         None => println!("Address not found on the blockchain")
                     }
 
+                    // reverse lookup of the names that resolve to the address
+                    let mut names = controller.blockchain_get_names(&address_arr);
+
+                    // when not running a local blockchain ask the peers, the first peer that responds is used
+                    if !controller.network_get_local_blockchain() {
+                        for peer in controller.network_get_peers() {
+                            if let Some(peer_names) = controller.network_get_names(&peer, &address_arr).await {
+                                names = peer_names;
+                                break;
+                            }
+                        }
+                    }
+
+                    if !names.is_empty() {
+                        println!("Names: {}", names.join(", "));
+                    }
+
                     println!();
                     break;
                 }
@@ -302,9 +414,147 @@ fn perform_blockhain_options(controller: &mut Controller) {
             },
             "9" | "9." | "view total loose change"  | "view loose change" | "loose change" | "change" => {
                 println!("Total loose change: {:.8} BLO", controller.blockchain_get_total_change() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                println!("Loose change recycled into the next block reward: {:.8} BLO", controller.blockchain_get_loose_change_reward() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                println!();
+            },
+            "10" | "10." | "view base fee" | "base fee" | "fee" => {
+                // display the fee every transaction in the next block must pay and the total amount of fees burned
+                println!("Base fee for block height {}: {:.8} BLO", controller.blockchain_get_block_height() + 1, controller.blockchain_get_base_fee());
+                println!("Total burned: {:.8} BLO", controller.blockchain_get_total_burned() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                println!();
+            },
+            "11" | "11." | "view supply" | "supply" => {
+                loop {
+                    // prompt the user for the block height of the supply theyd like to view, defaulting to the current block height
+                    println!("Enter the block height (leave blank for the current block height):");
+                    let block_height_input = read_string();
+                    let block_height = match block_height_input.as_str() {
+                        "" => controller.blockchain_get_block_height(),
+                        _ => match block_height_input.parse::<u64>() {
+                            Ok(block_height) => block_height,
+                            Err(_) => {
+                                println!("Enter a valid non-negative block height");
+                                println!();
+                                continue
+                            }
+                        }
+                    };
+                    println!();
+
+                    // if the block height is too high re-ask the user
+                    let supply = match controller.blockchain_get_supply(block_height) {
+                        Some(supply) => supply,
+                        None => {
+                            println!("Max block height: {}", controller.blockchain_get_block_height());
+                            continue;
+                        }
+                    };
+
+                    // display the supply breakdown at that block height
+                    println!("Supply at block height {}:", supply.get_block_height());
+                    println!("\tIssued: {:.8} BLO", supply.get_issued() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    println!("\tCirculating: {:.8} BLO", supply.get_circulating() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    println!("\tStaked: {:.8} BLO", supply.get_staked() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    println!("\tUnbonding: {:.8} BLO", supply.get_unbonding() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    println!("\tBurned: {:.8} BLO", supply.get_burned() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    println!("\tLost: {:.8} BLO", supply.get_lost() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    println!("Current supply conserved: {}", controller.blockchain_is_supply_conserved());
+                    println!();
+
+                    break;
+                }
+            },
+            "12" | "12." | "view proposer schedule" | "proposer schedule" | "schedule" => {
+                // use the local blockchain's schedule unless a peer reports theirs
+                let mut schedule = controller.blockchain_get_proposer_schedule();
+                let mut probability = controller.blockchain_get_proposer_probability();
+
+                // when not running a local blockchain ask the peers for the schedule, the first peer that responds is used
+                if !controller.network_get_local_blockchain() {
+                    for peer in controller.network_get_peers() {
+                        if let Some((peer_schedule, peer_probability)) = controller.network_get_proposer_schedule(&peer).await {
+                            println!("Proposer schedule reported by peer {}:{}", peer.get_ip(), peer.get_port());
+                            schedule = peer_schedule;
+                            probability = peer_probability;
+                            break;
+                        }
+                    }
+                }
+
+                // display the validators that may propose the next block in order, the first validator is the chosen proposer and the rest are fallbacks
+                println!("Proposer schedule for block height {}:", controller.blockchain_get_block_height() + 1);
+                for (index, (proposer_address, earliest_timestamp)) in schedule.iter().enumerate() {
+                    let marker = if *proposer_address == controller.wallet_get_address() { " (you)" } else { "" };
+                    println!("\t{}: {}{} earliest timestamp {}", index + 1, String::from_utf8_lossy(proposer_address), marker, earliest_timestamp);
+                }
+                println!("Probability of being the chosen proposer of a block: {:.4}%", probability * 100.0);
+                println!();
+            },
+            "13" | "13." | "view epoch" | "epoch" => {
+                // prompt the user for the epoch theyd like to view
+                let current_epoch_number = controller.blockchain_get_current_epoch().map(|epoch| epoch.get_number()).unwrap_or_default();
+                println!("Enter the epoch number or leave blank for the current epoch ({}):", current_epoch_number);
+                let epoch_input = read_string();
+                println!();
+
+                let epoch_number = if epoch_input.is_empty() {
+                    current_epoch_number
+                } else {
+                    match epoch_input.parse::<u64>() {
+                        Ok(epoch_number) => epoch_number,
+                        Err(_) => {
+                            println!("Enter a valid non-negative epoch number");
+                            println!();
+                            continue
+                        }
+                    }
+                };
+
+                let epoch = match controller.blockchain_get_epoch(epoch_number) {
+                    Some(epoch) => epoch,
+                    None => {
+                        println!("Epoch {} hasn't started yet", epoch_number);
+                        println!();
+                        continue
+                    }
+                };
+
+                // display the validator set frozen for the epoch and the share of the total weight each validator holds
+                println!("Epoch {} validators for block heights {} to {}:", epoch.get_number(), epoch.get_start_height(), epoch.get_end_height());
+                for (validator_address, validator_weight) in controller.blockchain_get_epoch_validators(&epoch).iter() {
+                    println!("\t{}: {:.8} BLO", String::from_utf8_lossy(validator_address), *validator_weight as f64 / LOWEST_DENOMINATION_PER_COIN);
+                }
+                println!("Total weight: {:.8} BLO", epoch.get_total_weight() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                println!();
+            },
+            "14" | "14." | "view finality" | "finality" => {
+                // display the last final block and how much of the validator weight has attested to the latest checkpoint
+                let checkpoint_height = controller.blockchain_get_latest_checkpoint_height();
+                let (attested_weight, total_weight) = controller.blockchain_get_attested_weight(checkpoint_height);
+                println!("Finalized block height: {}", controller.blockchain_get_finalized_height());
+                println!("Latest checkpoint block height: {}", checkpoint_height);
+                println!("Last trusted checkpoint block height: {}", controller.blockchain_get_last_trusted_checkpoint_height());
+                if total_weight != 0 {
+                    println!("Attested to latest checkpoint: {:.2}% of validator weight, 66.67% required", attested_weight as f64 / total_weight as f64 * 100.0);
+                }
+                println!();
+            },
+            "15" | "15." | "attest checkpoint" | "attest" => {
+                let attested = controller.validator_attest_checkpoint().await;
+                if attested {
+                    println!("Attested to checkpoint block at height {}", controller.blockchain_get_latest_checkpoint_height());
+                    println!();
+                }
+            },
+            "16" | "16." | "view deployments" | "deployments" => {
+                // display every protocol upgrade deployment and how many blocks of the current window have signaled for it
+                println!("Deployments (window of {} blocks, {} signals required to lock in):", *DEPLOYMENT_WINDOW, *DEPLOYMENT_THRESHOLD);
+                for (name, bit, state, since_height, signals) in controller.blockchain_get_deployments().iter() {
+                    println!("\t{}: bit {}, {:?} since block height {}, {} signals in the current window", name, bit, state, since_height, signals);
+                }
                 println!();
             },
-            "10" | "10." | "prune mempool" => {
+            "17" | "17." | "prune mempool" => {
                 // confirm with user that they want to clear the mempool
                 println!("This will clear your current mempool, you will not be able to confirm/view any of the previously broadcasted to you");
                 println!("Do you want to clear your mempool? (yes/no)");
@@ -320,7 +570,7 @@ fn perform_blockhain_options(controller: &mut Controller) {
                 println!("Mempool pruned");
                 println!();
             },
-            "11" | "11." | "back" => {
+            "18" | "18." | "back" => {
                 break;
             },
             _ => {}
@@ -329,7 +579,7 @@ fn perform_blockhain_options(controller: &mut Controller) {
 }
 
 async fn perform_transaction_options(controller: &mut Controller) {
-    let transaction_options = vec!["Transaction Options:", "A -> B", "Validator enable", "Validator revoke", "Back"];
+    let transaction_options = vec!["Transaction Options:", "A -> B", "Validator enable", "Validator revoke", "Validator increase stake", "Validator withdraw stake", "Delegate", "Undelegate", "Validator set commission", "Multisig", "Batch payment", "Bump fee", "Hash time lock", "Payment channel", "Rotate key", "Name registry", "Back"];
 
     loop {
         // present transaction options to user
@@ -343,7 +593,7 @@ async fn perform_transaction_options(controller: &mut Controller) {
             "1" | "1." | "a -> b" | "a->b" | "ab" | "a b" | "a" | "b" => {
                 loop {
                     // prompt the user for the recipient address
-                    println!("Enter the recipient (case sensitive) or \"exit\":");
+                    println!("Enter the recipient (case sensitive) or a registered name or \"exit\":");
                     let address_string = read_string();
                     println!();
 
@@ -352,6 +602,8 @@ async fn perform_transaction_options(controller: &mut Controller) {
                         break 
                     }
 
+                    let address_string = resolve_name(controller, address_string).await;
+
                     // attempt to convert the address string to a vector
                     let address_arr: [u8; BLOCK_ADDRESS_SIZE] = match address_string.as_bytes().try_into() {
                         Ok(address ) => address,
@@ -414,6 +666,7 @@ async fn perform_transaction_options(controller: &mut Controller) {
                         
                         loop {
                             // prompt the user for the fee theyd like to use
+                            println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
                             println!("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":");
                             let fee_string = read_string();
                             println!();
@@ -453,8 +706,21 @@ async fn perform_transaction_options(controller: &mut Controller) {
                                 continue;
                             }
 
+                            // prompt the user for an optional validity window
+                            let (valid_after, valid_until) = match read_validity_window(controller) {
+                                Some(validity_window) => validity_window,
+                                None => break
+                            };
+
+                            // prompt the user for an optional memo
+                            let memo = match read_memo(controller) {
+                                Some(memo) => memo,
+                                None => break
+                            };
+
                             // create the transaction
-                            let transaction = match controller.transaction_create_a_b(address_arr, (amount * *LOWEST_DENOMINATION_PER_COIN).ceil() as u64, (fee * *LOWEST_DENOMINATION_PER_COIN).ceil() as u64) {
+                            let tx_options = TxOptions::new().with_validity_window(valid_after, valid_until).with_memo(memo);
+                            let transaction = match controller.transaction_create_a_b(address_arr, (amount * *LOWEST_DENOMINATION_PER_COIN).ceil() as u64, (fee * *LOWEST_DENOMINATION_PER_COIN).ceil() as u64, tx_options) {
                                 Some(transaction) => transaction,
                                 None => {
                                     println!("Failed creating transaction, check wallet file location/permissions");
@@ -488,6 +754,7 @@ async fn perform_transaction_options(controller: &mut Controller) {
                                     // increment wallet nonce
                                     controller.wallet_increment_nonce();
                                     println!("Successfully added transaction to mempool and broadcasted transaction to: {:?}", successful_broadcasted_peers);
+                                    println!("Transaction ID: {}", encode_hex(&transaction.serialize_hash_txid()));
                                     println!();
                                 } else {
                                     // if unable to broadcast transaction remove transaction from local mempool to keep in sync with the network
@@ -499,6 +766,7 @@ async fn perform_transaction_options(controller: &mut Controller) {
                                 // increment wallet nonce
                                 controller.wallet_increment_nonce();
                                 println!("Successfully added transaction to mempool");
+                                println!("Transaction ID: {}", encode_hex(&transaction.serialize_hash_txid()));
                                 println!();
                             }
                             break;
@@ -554,6 +822,7 @@ async fn perform_transaction_options(controller: &mut Controller) {
                     
                     loop {
                         // prompt the user for the fee theyd like to use
+                        println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
                         println!("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":");
                         let fee_string = read_string();
                         println!();
@@ -586,6 +855,14 @@ async fn perform_transaction_options(controller: &mut Controller) {
                             }
                         }
 
+                        // confirm the fee is at least the minimum validator enable fee, which is burned
+                        let minimum_fee = controller.blockchain_get_minimum_validator_enable_fee();
+                        if fee < minimum_fee {
+                            println!("The minimum fee for a validator enable transaction is {:.8} BLO, this part of the fee is burned", minimum_fee);
+                            println!();
+                            continue;
+                        }
+
                         // confirm the user has sufficient funds
                         if fee > balance - amount {
                             println!("Insufficient funds");
@@ -664,11 +941,13 @@ async fn perform_transaction_options(controller: &mut Controller) {
                         }
                     };
 
-                    println!("This will revoke your entire stake of: {:.8} BLO", account.get_stake());
+                    println!("This will revoke your entire stake of: {:.8} BLO", account.get_stake() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    println!("Your stake will be locked for {} blocks after the revoke is confirmed before it is returned to your balance", *UNBONDING_PERIOD);
 
                     // prompt the user for the fee theyd like to use
                     let balance = controller.wallet_get_balance();
                     println!("Current balance: {:.8} BLO", balance);
+                    println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
                     println!("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":");
                     let fee_string = read_string();
                     println!();
@@ -759,124 +1038,1238 @@ async fn perform_transaction_options(controller: &mut Controller) {
                     break;
                 }
             },
-            "4" | "4." | "back" => {
-                break;
+            "4" | "4." | "validator increase stake" | "increase stake" | "increase" => {
+                // check if the account is currently staking
+                match controller.blockchain_get_account(&controller.wallet_get_address()) {
+                    Some(account) => {
+                        if !account.get_validator() {
+                            println!("You are not currently a validator on the blockchain, use a validator enable transaction to begin staking");
+                            println!();
+                            continue;
+                        }
+                        println!("Current stake: {:.8} BLO", account.get_stake() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    },
+                    None => {
+                        println!("You are not currently a validator on the blockchain, use a validator enable transaction to begin staking");
+                        println!();
+                        continue;
+                    }
+                };
+
+                // prompt the user for the amount theyd like to add to their stake
+                println!("Current balance: {:.8} BLO", controller.wallet_get_balance());
+                let amount = match read_blo_amount("Enter the amount of BLO you'd like to add to your stake or \"exit\":", false) {
+                    Some(amount) => amount,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_validator_stake_increase(amount, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
             },
-            _ => {}
-        }
-    }
-}
+            "5" | "5." | "validator withdraw stake" | "withdraw stake" | "withdraw" => {
+                // check if the account is currently staking
+                match controller.blockchain_get_account(&controller.wallet_get_address()) {
+                    Some(account) => {
+                        if !account.get_validator() {
+                            println!("You are not currently a validator on the blockchain");
+                            println!();
+                            continue;
+                        }
+                        println!("Current stake: {:.8} BLO", account.get_stake() as f64 / LOWEST_DENOMINATION_PER_COIN);
+                    },
+                    None => {
+                        println!("You are not currently a validator on the blockchain");
+                        println!();
+                        continue;
+                    }
+                };
+
+                println!("Withdrawn stake will be locked for {} blocks after the withdraw is confirmed before it is returned to your balance, use a validator revoke transaction to withdraw your entire stake", *UNBONDING_PERIOD);
+
+                // prompt the user for the amount theyd like to withdraw from their stake
+                let amount = match read_blo_amount("Enter the amount of BLO you'd like to withdraw from your stake or \"exit\":", false) {
+                    Some(amount) => amount,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_validator_stake_withdraw(amount, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
 
-async fn perform_network_options(controller: &mut Controller) {
-    let network_options = vec!["Network Options", "View Peers", "Ping Peer", "Add Peer", "Remove Peer", "Back"];
+                submit_transaction(controller, &transaction).await;
+            },
+            "6" | "6." | "delegate" => {
+                // prompt the user for the validator theyd like to delegate to
+                let validator_address = match read_address(controller, "Enter the address of the validator you'd like to delegate to (case sensitive) or \"exit\":").await {
+                    Some(validator_address) => validator_address,
+                    None => continue
+                };
+
+                // confirm the address belongs to a current validator
+                match controller.blockchain_get_validator(&validator_address) {
+                    Some(validator) => println!("Validator commission: {:.2}%", validator.get_commission() as f64 / 100.0),
+                    None => {
+                        println!("That address is not currently a validator on the blockchain");
+                        println!();
+                        continue;
+                    }
+                };
+
+                // prompt the user for the amount theyd like to delegate
+                println!("Current balance: {:.8} BLO", controller.wallet_get_balance());
+                let amount = match read_blo_amount("Enter the amount of BLO you'd like to delegate or \"exit\":", false) {
+                    Some(amount) => amount,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_delegate(validator_address, amount, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
 
-    loop {
-        // present network options to user
-        print_options(&network_options);
+                submit_transaction(controller, &transaction).await;
+            },
+            "7" | "7." | "undelegate" => {
+                // display the current delegations
+                let delegations = controller.blockchain_get_delegations(&controller.wallet_get_address());
+                if delegations.is_empty() {
+                    println!("You have not delegated to any validators");
+                    println!();
+                    continue;
+                }
+                for (validator_address, amount) in delegations.iter() {
+                    println!("{:.8} BLO delegated to {}", *amount as f64 / LOWEST_DENOMINATION_PER_COIN, String::from_utf8_lossy(validator_address));
+                }
+                println!();
 
-        // get user choice
-        let option_input = read_string().to_lowercase();
-        println!();
+                // prompt the user for the validator theyd like to undelegate from
+                let validator_address = match read_address(controller, "Enter the address of the validator you'd like to undelegate from (case sensitive) or \"exit\":").await {
+                    Some(validator_address) => validator_address,
+                    None => continue
+                };
+
+                println!("Undelegated funds will be locked for {} blocks after the undelegation is confirmed before they are returned to your balance", *UNBONDING_PERIOD);
+
+                // prompt the user for the amount theyd like to undelegate
+                let amount = match read_blo_amount("Enter the amount of BLO you'd like to undelegate or \"exit\":", false) {
+                    Some(amount) => amount,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_undelegate(validator_address, amount, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
 
-        match option_input.as_str() {
-            "1" | "1." | "view" | "view peers" => {
-                println!("Peer list:");
-                println!("{:?}", controller.network_get_peers());
-                println!();
+                submit_transaction(controller, &transaction).await;
             },
-            "2" | "2." | "ping" | "ping peer" => {
-                loop {
-                    // display a list of peers to the user and a user enter peer inforamtion option
-                    println!("Choice a peer from the list below or choose other to ping a peer not in the list or \"exit\"");
-                    for (index, peer) in controller.network_get_peers().iter().enumerate() {
-                        println!("{}. {:?}", index + 1, peer);
+            "8" | "8." | "validator set commission" | "set commission" | "commission" => {
+                // check if the account is currently a validator and display their current commission
+                match controller.blockchain_get_validator(&controller.wallet_get_address()) {
+                    Some(validator) => println!("Current commission: {:.2}%", validator.get_commission() as f64 / 100.0),
+                    None => {
+                        println!("You are not currently a validator on the blockchain");
+                        println!();
+                        continue;
                     }
-                    let total_peers = controller.network_get_peers().len();
-                    println!("{}. Enter peer information", total_peers + 1);
-                    let peer_input = read_string().to_lowercase();
-                    println!();
+                };
 
-                    if peer_input.to_lowercase() == "exit" {
-                        break;
+                // prompt the user for the commission as a percentage, stored on chain in basis points
+                println!("Enter the percentage of your delegators' rewards you'd like to keep as commission (0 - 100, maximum of 2 decimal places) or \"exit\":");
+                let commission_string = read_string();
+                println!();
+
+                if commission_string.to_lowercase() == "exit" {
+                    continue;
+                }
+
+                let commission_parts = commission_string.split('.').collect::<Vec<&str>>();
+                let commission = match commission_string.parse::<f64>() {
+                    Ok(commission) if (0.0..=100.0).contains(&commission) && (commission_parts.len() == 1 || commission_parts[1].len() <= 2) => (commission * 100.0).round() as u16,
+                    _ => {
+                        println!("Enter a percentage between 0 and 100 with a maximum of 2 decimal places");
+                        println!();
+                        continue;
+                    }
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_validator_commission(commission, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
                     }
+                };
 
-                    // convert user input to an index
-                    let peer_selection = match peer_input.parse::<usize>() {
-                        Ok(fee) => fee,
-                        Err(_) => continue
-                    };
+                submit_transaction(controller, &transaction).await;
+            },
+            "9" | "9." | "multisig" => {
+                perform_multisig_options(controller).await;
+            },
+            "10" | "10." | "batch payment" | "batch" => {
+                // batch transactions can only be used once the batch deployment is active
+                if !controller.blockchain_is_deployment_active("batch") {
+                    println!("Batch payments aren't active on the blockchain yet, check the batch deployment in the blockchain options");
+                    println!();
+                    continue;
+                }
 
-                    if peer_selection > 0 && peer_selection < total_peers + 1 {
-                        // if user input is one of the peers in the peer list
-                        // ping the peer they selected
-                        let mut peer = controller.network_get_peers()[peer_selection - 1].clone();
+                let outputs = match read_batch_outputs(controller) {
+                    Some(outputs) => outputs,
+                    None => continue
+                };
 
-                        if !controller.network_ping_peer(&mut peer).await {
-                            println!("Unable to ping peer {}:{}", peer.get_ip(), peer.get_port());
-                            println!();
-                        } else {
-                            println!("Successfully pinged peer {}:{}", peer.get_ip(), peer.get_port());
-                            println!();
-                        }
-                        break;
-                    } else if peer_selection == total_peers + 1 {
-                        // if user chose to enter a specific peer's ipv4:port combo
-                        // prompt user to enter peer information
-                        loop {
-                            println!("Enter peer information in ipv4:port format or \"exit\"");
-                            let peer_information = read_string();
-                            println!();
+                // display the total of the outputs and confirm the user has sufficient funds
+                let amount = outputs.iter().fold(0u64, |total, (_, amount)| total.saturating_add(*amount));
+                println!("Paying {:.8} BLO to {} recipients", amount as f64 / *LOWEST_DENOMINATION_PER_COIN, outputs.len());
+                println!("Current balance: {:.8} BLO", controller.wallet_get_balance());
+                println!();
 
-                            if peer_information.to_lowercase() == "exit" {
-                                break;
-                            }
+                // the base fee is paid once for each output
+                let minimum_fee = controller.blockchain_get_base_fee() * outputs.len() as f64;
+                println!("Minimum fee for {} outputs: {:.8} BLO, transactions with a lower fee won't be added to a block", outputs.len(), minimum_fee);
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
 
-                            let mut peer = match Peer::new(peer_information.as_str()) {
-                                Some(peer) => peer,
-                                None => {
-                                    println!("Invalid ipv4:port entered");
-                                    println!();
-                                    continue;
-                                }
-                            };
+                if amount.saturating_add(fee) as f64 / *LOWEST_DENOMINATION_PER_COIN > controller.wallet_get_balance() {
+                    println!("Insufficient funds");
+                    println!();
+                    continue;
+                }
 
-                            if !controller.network_ping_peer(&mut peer).await {
-                                println!("Unable to ping peer {}:{}", peer.get_ip(), peer.get_port());
-                                println!();
-                            } else {
-                                println!("Successfully pinged peer {}:{}", peer.get_ip(), peer.get_port());
-                                println!();
-                            }
-                            break;
-                        }
-                        break;
-                    } else {
-                        // if user entered invalid input
-                        continue;
+                // create the transaction
+                let transaction = match controller.transaction_create_batch(outputs, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
                     }
-                }
+                };
+
+                submit_transaction(controller, &transaction).await;
             },
-            "3" | "3." | "add" | "add peer" => {
-                // prompt user to enter peer information
-                loop {
-                    println!("Enter peer information of the peer to add in ipv4:port format or \"exit\"");
-                    let peer_information = read_string();
+            "11" | "11." | "bump fee" | "bump" => {
+                // display the wallet's transactions that are still waiting in the mempool
+                let pending_transactions = controller.blockchain_get_mempool().remove(&controller.wallet_get_address()).unwrap_or_default();
+                if pending_transactions.is_empty() {
+                    println!("You have no pending transactions in the mempool");
                     println!();
+                    continue;
+                }
 
-                    if peer_information.to_lowercase() == "exit" {
-                        break;
-                    }
+                println!("Pending transactions:");
+                for transaction in pending_transactions.iter() {
+                    println!("\tNonce {}: {:.8} BLO to {} with a fee of {:.8} BLO", transaction.nonce, transaction.amount as f64 / *LOWEST_DENOMINATION_PER_COIN, String::from_utf8_lossy(&transaction.recipient), transaction.fee as f64 / *LOWEST_DENOMINATION_PER_COIN);
+                }
+                println!();
 
-                    let mut peer = match Peer::new(peer_information.as_str()) {
-                        Some(peer) => peer,
-                        None => {
-                            println!("Invalid ipv4:port entered");
-                            println!();
-                            continue;
-                        }
-                    };
+                // prompt the user for the nonce of the transaction theyd like to bump
+                println!("Enter the nonce of the transaction you'd like to bump the fee of or \"exit\":");
+                let nonce_string = read_string();
+                println!();
 
-                    // ping peer before adding them to the peer list
+                if nonce_string.to_lowercase() == "exit" {
+                    continue;
+                }
+
+                let transaction = match nonce_string.parse::<u64>().ok().and_then(|nonce| pending_transactions.iter().find(|transaction| transaction.nonce == nonce)) {
+                    Some(transaction) => transaction.clone(),
+                    None => {
+                        println!("No pending transaction with that nonce");
+                        println!();
+                        continue;
+                    }
+                };
+
+                // the replacement must pay a sufficiently higher fee than the pending transaction
+                let minimum_fee = controller.blockchain_get_minimum_replacement_fee(&transaction);
+                println!("Minimum replacement fee: {:.8} BLO", minimum_fee as f64 / *LOWEST_DENOMINATION_PER_COIN);
+                let fee = match read_blo_amount("Enter the new fee you'd like to attach to your transaction (in BLO) or \"exit\":", false) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                if fee < minimum_fee {
+                    println!("The new fee must be at least the minimum replacement fee");
+                    println!();
+                    continue;
+                }
+
+                let replacement = match controller.transaction_bump_fee(&transaction, fee) {
+                    Some(replacement) => replacement,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_replacement_transaction(controller, &transaction, &replacement).await;
+            },
+            "12" | "12." | "hash time lock" | "htlc" => {
+                // hash time-locked transfers can only be used once the htlc deployment is active
+                if !controller.blockchain_is_deployment_active("htlc") {
+                    println!("Hash time-locked transfers aren't active on the blockchain yet, check the htlc deployment in the blockchain options");
+                    println!();
+                    continue;
+                }
+
+                perform_htlc_options(controller).await;
+            },
+            "13" | "13." | "payment channel" | "channel" => {
+                // payment channels can only be used once the payment_channel deployment is active
+                if !controller.blockchain_is_deployment_active("payment_channel") {
+                    println!("Payment channels aren't active on the blockchain yet, check the payment_channel deployment in the blockchain options");
+                    println!();
+                    continue;
+                }
+
+                perform_payment_channel_options(controller).await;
+            },
+            "14" | "14." | "rotate key" | "rotate" => {
+                // keys can only be rotated once the key_rotation deployment is active
+                if !controller.blockchain_is_deployment_active("key_rotation") {
+                    println!("Key rotation isn't active on the blockchain yet, check the key_rotation deployment in the blockchain options");
+                    println!();
+                    continue;
+                }
+
+                // prompt the user for the public key to rotate the account to
+                println!("The account keeps its address, funds and validator status, but only the new key can sign for it once the transaction has been added to a block");
+                println!("Enter the new public key in hex, leave blank to generate a new key or \"exit\":");
+                let public_key_string = read_string();
+                println!();
+
+                if public_key_string.to_lowercase() == "exit" {
+                    continue;
+                }
+
+                let new_public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = if public_key_string.is_empty() {
+                    // confirm with user that sensitive data is about to be displayed
+                    let (wif_private_key, new_public_key) = controller.wallet_generate_rotation_key();
+                    println!("The new private key is about to be displayed, it is sensitive information that no one should view besides yourself, please enter any character when you are ready");
+                    read_string();
+                    println!("New Private Key (WIF): {}", wif_private_key);
+                    println!("Store the new private key safely, it is not saved to the wallet file until you import it in the wallet options once the transaction has been added to a block");
+                    println!();
+                    new_public_key
+                } else {
+                    match decode_hex(&public_key_string).and_then(|public_key| public_key.try_into().ok()) {
+                        Some(new_public_key) => new_public_key,
+                        None => {
+                            println!("Invalid public key, enter a {} byte compressed public key in hex", COMPRESSED_PUBLIC_KEY_SIZE);
+                            println!();
+                            continue;
+                        }
+                    }
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_key_rotation(new_public_key, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "15" | "15." | "name registry" | "names" | "name" => {
+                // names can only be registered once the name_registry deployment is active
+                if !controller.blockchain_is_deployment_active("name_registry") {
+                    println!("The name registry isn't active on the blockchain yet, check the name_registry deployment in the blockchain options");
+                    println!();
+                    continue;
+                }
+
+                perform_name_options(controller).await;
+            },
+            "16" | "16." | "back" => {
+                break;
+            },
+            _ => {}
+        }
+    }
+}
+
+async fn perform_multisig_options(controller: &mut Controller) {
+    let multisig_options = vec!["Multisig Options:", "Create multisig address", "Create multisig transaction", "Sign multisig transaction", "Submit multisig transaction", "Back"];
+
+    loop {
+        // present multisig options to user
+        print_options(&multisig_options);
+
+        // get user choice
+        let option_input = read_string().to_lowercase();
+        println!();
+
+        match option_input.as_str() {
+            "1" | "1." | "create multisig address" | "address" => {
+                let (threshold, public_keys) = match read_multisig_keys(controller) {
+                    Some(multisig_keys) => multisig_keys,
+                    None => continue
+                };
+
+                println!("Multisig address: {}", String::from_utf8_lossy(&controller.wallet_generate_multisig_address(threshold, &public_keys)));
+                println!();
+            },
+            "2" | "2." | "create multisig transaction" | "create" => {
+                let (threshold, public_keys) = match read_multisig_keys(controller) {
+                    Some(multisig_keys) => multisig_keys,
+                    None => continue
+                };
+
+                // the wallet signs the transaction first, so it must be one of the keys
+                if !public_keys.contains(&controller.wallet_get_public_key()) {
+                    println!("Your public key must be one of the multisig public keys");
+                    println!();
+                    continue;
+                }
+
+                // display the balance of the multisig account
+                let multisig_address = controller.wallet_generate_multisig_address(threshold, &public_keys);
+                let balance = controller.blockchain_get_account(&multisig_address).map(|account| account.get_balance()).unwrap_or(0);
+                println!("Multisig address: {}", String::from_utf8_lossy(&multisig_address));
+                println!("Multisig balance: {:.8} BLO", balance as f64 / *LOWEST_DENOMINATION_PER_COIN);
+                println!();
+
+                let recipient = match read_address(controller, "Enter the recipient (case sensitive) or \"exit\":").await {
+                    Some(recipient) => recipient,
+                    None => continue
+                };
+
+                let amount = match read_blo_amount("Enter the amount of BLO you'd like to send or \"exit\":", false) {
+                    Some(amount) => amount,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // confirm the multisig account has sufficient funds
+                if amount + fee > balance {
+                    println!("Insufficient funds");
+                    println!();
+                    continue;
+                }
+
+                match controller.transaction_create_multisig(recipient, amount, fee, threshold, &public_keys) {
+                    Some(transaction) => print_multisig_transaction(&transaction),
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                    }
+                };
+            },
+            "3" | "3." | "sign multisig transaction" | "sign" => {
+                let transaction = match read_multisig_transaction() {
+                    Some(transaction) => transaction,
+                    None => continue
+                };
+
+                // add the wallet's signature to the transaction
+                match controller.transaction_sign_multisig(&transaction) {
+                    Some(transaction) => print_multisig_transaction(&transaction),
+                    None => {
+                        println!("Failed signing transaction, your public key must be one of the multisig public keys");
+                        println!();
+                    }
+                };
+            },
+            "4" | "4." | "submit multisig transaction" | "submit" => {
+                let transaction = match read_multisig_transaction() {
+                    Some(transaction) => transaction,
+                    None => continue
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "5" | "5." | "back" => {
+                break;
+            },
+            _ => {}
+        }
+    }
+}
+
+async fn perform_htlc_options(controller: &mut Controller) {
+    let htlc_options = vec!["Hash Time Lock Options:", "View transfers", "Lock", "Claim", "Refund", "Back"];
+
+    loop {
+        // present hash time lock options to user
+        print_options(&htlc_options);
+
+        // get user choice
+        let option_input = read_string().to_lowercase();
+        println!();
+
+        match option_input.as_str() {
+            "1" | "1." | "view transfers" | "transfers" | "view" => {
+                // display the transfers the wallet has locked or can claim
+                let htlcs = controller.blockchain_get_htlcs(&controller.wallet_get_address());
+                if htlcs.is_empty() {
+                    println!("You have no open hash time-locked transfers");
+                    println!();
+                    continue;
+                }
+
+                for (lock_id, htlc) in htlcs.iter() {
+                    println!("Lock ID: {}", encode_hex(lock_id));
+                    println!("	{:.8} BLO from {} to {}", htlc.get_amount() as f64 / *LOWEST_DENOMINATION_PER_COIN, String::from_utf8_lossy(&htlc.get_sender()), String::from_utf8_lossy(&htlc.get_recipient()));
+                    println!("	Hash lock: {}", encode_hex(&htlc.get_hash_lock()));
+                    println!("	Claimable until block height {}, refundable from then on", htlc.get_refund_height());
+                }
+                println!();
+            },
+            "2" | "2." | "lock" => {
+                // prompt the user for the address that can claim the funds
+                let recipient = match read_address(controller, "Enter the address that can claim the funds (case sensitive) or \"exit\":").await {
+                    Some(recipient) => recipient,
+                    None => continue
+                };
+
+                // prompt the user for the amount theyd like to lock
+                println!("Current balance: {:.8} BLO", controller.wallet_get_balance());
+                let amount = match read_blo_amount("Enter the amount of BLO you'd like to lock or \"exit\":", false) {
+                    Some(amount) => amount,
+                    None => continue
+                };
+
+                // prompt the user for the hash lock, the side that starts an atomic swap generates the secret and the other side uses its hash lock
+                let hash_lock = match read_hash("Enter the hash lock in hex, leave blank to generate a new secret or \"exit\":", true) {
+                    Some(Some(hash_lock)) => hash_lock,
+                    Some(None) => {
+                        let preimage = controller.wallet_generate_htlc_preimage();
+                        let hash_lock = Htlc::hash_preimage(&preimage);
+                        println!("Secret (preimage): {}", encode_hex(&preimage));
+                        println!("Hash lock: {}", encode_hex(&hash_lock));
+                        println!("Keep the secret safe, anyone who knows it can claim funds locked with its hash lock");
+                        println!();
+                        hash_lock
+                    },
+                    None => continue
+                };
+
+                // prompt the user for the refund height, the funds can be claimed until the block before it
+                let block_height = controller.blockchain_get_block_height();
+                println!("Current block height: {}", block_height);
+                let refund_height = loop {
+                    println!("Enter the block height from which you can refund the funds if they haven't been claimed or \"exit\":");
+                    let refund_height_string = read_string();
+                    println!();
+
+                    if refund_height_string.to_lowercase() == "exit" {
+                        break None
+                    }
+
+                    match refund_height_string.parse::<u64>() {
+                        Ok(refund_height) if refund_height > block_height + 1 => break Some(refund_height),
+                        _ => {
+                            println!("Enter a block height larger than {}", block_height + 1);
+                            println!();
+                        }
+                    }
+                };
+                let refund_height = match refund_height {
+                    Some(refund_height) => refund_height,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                if amount.saturating_add(fee) as f64 / *LOWEST_DENOMINATION_PER_COIN > controller.wallet_get_balance() {
+                    println!("Insufficient funds");
+                    println!();
+                    continue;
+                }
+
+                // create the transaction, its transaction id is the lock id used to claim or refund the funds
+                let transaction = match controller.transaction_create_htlc_lock(recipient, amount, hash_lock, refund_height, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "3" | "3." | "claim" => {
+                let (lock_id, htlc) = match read_htlc(controller) {
+                    Some(htlc) => htlc,
+                    None => continue
+                };
+
+                // only the transfer's recipient can claim it, before its refund height
+                if htlc.get_recipient() != controller.wallet_get_address() {
+                    println!("Only {} can claim this transfer", String::from_utf8_lossy(&htlc.get_recipient()));
+                    println!();
+                    continue;
+                }
+                if controller.blockchain_get_block_height() + 1 >= htlc.get_refund_height() {
+                    println!("This transfer can no longer be claimed, it expired at block height {}", htlc.get_refund_height());
+                    println!();
+                    continue;
+                }
+
+                // prompt the user for the secret that unlocks the transfer
+                let preimage = match read_hash("Enter the secret (preimage) in hex or \"exit\":", false) {
+                    Some(Some(preimage)) => preimage,
+                    _ => continue
+                };
+
+                if !htlc.is_unlocked_by(&preimage) {
+                    println!("The secret doesn't match the transfer's hash lock");
+                    println!();
+                    continue;
+                }
+
+                // prompt the user for the fee theyd like to use, it can be paid out of the claimed funds
+                println!("Claiming {:.8} BLO", htlc.get_amount() as f64 / *LOWEST_DENOMINATION_PER_COIN);
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_htlc_claim(lock_id, preimage, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "4" | "4." | "refund" => {
+                let (lock_id, htlc) = match read_htlc(controller) {
+                    Some(htlc) => htlc,
+                    None => continue
+                };
+
+                // only the transfer's sender can refund it, from its refund height
+                if htlc.get_sender() != controller.wallet_get_address() {
+                    println!("Only {} can refund this transfer", String::from_utf8_lossy(&htlc.get_sender()));
+                    println!();
+                    continue;
+                }
+                if controller.blockchain_get_block_height() + 1 < htlc.get_refund_height() {
+                    println!("This transfer can't be refunded until block height {}", htlc.get_refund_height());
+                    println!();
+                    continue;
+                }
+
+                // prompt the user for the fee theyd like to use, it can be paid out of the refunded funds
+                println!("Refunding {:.8} BLO", htlc.get_amount() as f64 / *LOWEST_DENOMINATION_PER_COIN);
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_htlc_refund(lock_id, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "5" | "5." | "back" => {
+                break;
+            },
+            _ => {}
+        }
+    }
+}
+
+async fn perform_payment_channel_options(controller: &mut Controller) {
+    let payment_channel_options = vec!["Payment Channel Options:", "View channels", "Open channel", "Create update", "Verify update", "Close channel", "Back"];
+
+    loop {
+        // present payment channel options to user
+        print_options(&payment_channel_options);
+
+        // get user choice
+        let option_input = read_string().to_lowercase();
+        println!();
+
+        match option_input.as_str() {
+            "1" | "1." | "view channels" | "channels" | "view" => {
+                // display the channels the wallet pays or is paid by
+                let payment_channels = controller.blockchain_get_payment_channels(&controller.wallet_get_address());
+                if payment_channels.is_empty() {
+                    println!("You have no open payment channels");
+                    println!();
+                    continue;
+                }
+
+                for (channel_id, channel) in payment_channels.iter() {
+                    println!("Channel ID: {}", encode_hex(channel_id));
+                    println!("	{:.8} BLO deposited by {} for {}", channel.get_deposit() as f64 / *LOWEST_DENOMINATION_PER_COIN, String::from_utf8_lossy(&channel.get_payer()), String::from_utf8_lossy(&channel.get_payee()));
+                    if let Some(settle_height) = channel.get_settle_height() {
+                        println!("	Closed by the payer paying {:.8} BLO, settles at block height {}", channel.get_paid() as f64 / *LOWEST_DENOMINATION_PER_COIN, settle_height);
+                    }
+                }
+                println!();
+            },
+            "2" | "2." | "open channel" | "open" => {
+                // prompt the user for the address the channel pays
+                let payee = match read_address(controller, "Enter the address the channel pays (case sensitive) or \"exit\":").await {
+                    Some(payee) => payee,
+                    None => continue
+                };
+
+                // prompt the user for the deposit theyd like to lock in the channel
+                println!("Current balance: {:.8} BLO", controller.wallet_get_balance());
+                let deposit = match read_blo_amount("Enter the amount of BLO you'd like to deposit into the channel or \"exit\":", false) {
+                    Some(deposit) => deposit,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                if deposit.saturating_add(fee) as f64 / *LOWEST_DENOMINATION_PER_COIN > controller.wallet_get_balance() {
+                    println!("Insufficient funds");
+                    println!();
+                    continue;
+                }
+
+                // create the transaction, its transaction id is the channel id channel updates are made for
+                let transaction = match controller.transaction_create_channel_open(payee, deposit, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "3" | "3." | "create update" | "create" => {
+                let (channel_id, channel) = match read_payment_channel(controller) {
+                    Some(payment_channel) => payment_channel,
+                    None => continue
+                };
+
+                // only the payer can pay with the channel
+                if channel.get_payer() != controller.wallet_get_address() {
+                    println!("Only {} can create updates for this channel", String::from_utf8_lossy(&channel.get_payer()));
+                    println!();
+                    continue;
+                }
+
+                // prompt the user for the total amount paid so far, each update replaces the last one
+                println!("Channel deposit: {:.8} BLO", channel.get_deposit() as f64 / *LOWEST_DENOMINATION_PER_COIN);
+                let paid = match read_blo_amount("Enter the total amount of BLO paid to the payee with this channel, including earlier updates, or \"exit\":", true) {
+                    Some(paid) => paid,
+                    None => continue
+                };
+
+                if paid > channel.get_deposit() {
+                    println!("An update can't pay more than the channel deposit");
+                    println!();
+                    continue;
+                }
+
+                match controller.wallet_create_channel_update(channel_id, paid) {
+                    Some(channel_update) => {
+                        println!("Send the update to the payee, it can close the channel with it at any time");
+                        println!("Channel update: {}", encode_hex(&channel_update.serialize_channel_update()));
+                        println!();
+                    },
+                    None => {
+                        println!("Failed creating channel update, check wallet file location/permissions");
+                        println!();
+                    }
+                };
+            },
+            "4" | "4." | "verify update" | "verify" => {
+                let channel_update = match read_channel_update() {
+                    Some(channel_update) => channel_update,
+                    None => continue
+                };
+
+                // confirm the update is signed by the payer of an open channel
+                if !controller.blockchain_verify_channel_update(&channel_update) {
+                    println!("Invalid channel update, the channel may not be open or the update isn't signed by its payer");
+                    println!();
+                    continue;
+                }
+
+                match controller.blockchain_get_payment_channel(&channel_update.get_channel_id()) {
+                    Some(channel) => {
+                        println!("Valid update for channel {}", encode_hex(&channel_update.get_channel_id()));
+                        println!("Pays {:.8} of {:.8} BLO to {}", channel_update.get_paid() as f64 / *LOWEST_DENOMINATION_PER_COIN, channel.get_deposit() as f64 / *LOWEST_DENOMINATION_PER_COIN, String::from_utf8_lossy(&channel.get_payee()));
+                        println!();
+                    },
+                    None => {
+                        println!("Invalid channel update, the channel may not be open or the update isn't signed by its payer");
+                        println!();
+                    }
+                };
+            },
+            "5" | "5." | "close channel" | "close" => {
+                let (channel_id, channel) = match read_payment_channel(controller) {
+                    Some(payment_channel) => payment_channel,
+                    None => continue
+                };
+
+                let channel_update = if channel.get_payee() == controller.wallet_get_address() {
+                    // the payee closes the channel with the latest update it received, the channel is settled right away
+                    let channel_update = match read_channel_update() {
+                        Some(channel_update) => channel_update,
+                        None => continue
+                    };
+
+                    if channel_update.get_channel_id() != channel_id || !controller.blockchain_verify_channel_update(&channel_update) {
+                        println!("Invalid channel update for this channel");
+                        println!();
+                        continue;
+                    }
+
+                    channel_update
+                } else if channel.get_payer() == controller.wallet_get_address() {
+                    // the payer can only close the channel once
+                    if channel.get_settle_height().is_some() {
+                        println!("You already closed this channel, it settles at block height {}", channel.get_settle_height().unwrap_or_default());
+                        println!();
+                        continue;
+                    }
+
+                    // the payer closes the channel with the amount of its latest update, the payee can close it with a later update during the dispute period
+                    println!("The channel settles {} blocks after it is closed, unless the payee closes it with a later update first", CHANNEL_DISPUTE_PERIOD);
+                    let paid = match read_blo_amount("Enter the total amount of BLO paid to the payee by your latest update or \"exit\":", true) {
+                        Some(paid) => paid,
+                        None => continue
+                    };
+
+                    if paid > channel.get_deposit() {
+                        println!("An update can't pay more than the channel deposit");
+                        println!();
+                        continue;
+                    }
+
+                    match controller.wallet_create_channel_update(channel_id, paid) {
+                        Some(channel_update) => channel_update,
+                        None => {
+                            println!("Failed creating channel update, check wallet file location/permissions");
+                            println!();
+                            continue
+                        }
+                    }
+                } else {
+                    println!("Only the payer or the payee can close this channel");
+                    println!();
+                    continue;
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_channel_close(channel_update, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "6" | "6." | "back" => {
+                break;
+            },
+            _ => {}
+        }
+    }
+}
+
+async fn perform_name_options(controller: &mut Controller) {
+    let name_options = vec!["Name Registry Options:", "View names", "Resolve name", "Register name", "Renew name", "Transfer name", "Back"];
+
+    loop {
+        // present name registry options to user
+        print_options(&name_options);
+
+        // get user choice
+        let option_input = read_string().to_lowercase();
+        println!();
+
+        match option_input.as_str() {
+            "1" | "1." | "view names" | "names" | "view" => {
+                // display the names that resolve to the wallet's address
+                let names = controller.blockchain_get_names(&controller.wallet_get_address());
+                if names.is_empty() {
+                    println!("You have no registered names");
+                    println!();
+                    continue;
+                }
+
+                for name in names.iter() {
+                    if let Some(name_record) = NameRecord::encode_name(name).and_then(|name| controller.blockchain_get_name_record(&name)) {
+                        println!("{} expires at block height {}", name, name_record.get_expiry_height());
+                    }
+                }
+                println!();
+            },
+            "2" | "2." | "resolve name" | "resolve" => {
+                let name = match read_name() {
+                    Some(name) => name,
+                    None => continue
+                };
+
+                // display the address the name resolves to, an expired name no longer resolves
+                match controller.blockchain_get_name_record(&name) {
+                    Some(name_record) if !name_record.is_expired(controller.blockchain_get_block_height() + 1) => {
+                        println!("{} resolves to {}", NameRecord::decode_name(&name), String::from_utf8_lossy(&name_record.get_owner()));
+                        println!("Expires at block height {}", name_record.get_expiry_height());
+                    },
+                    Some(_) => println!("{} has expired, it can be registered again", NameRecord::decode_name(&name)),
+                    None => println!("{} isn't registered", NameRecord::decode_name(&name))
+                }
+                println!();
+            },
+            "3" | "3." | "register name" | "register" => {
+                let name = match read_name() {
+                    Some(name) => name,
+                    None => continue
+                };
+
+                // only names that aren't registered or have expired can be registered
+                if controller.blockchain_resolve_name(&NameRecord::decode_name(&name)).is_some() {
+                    println!("{} is already registered", NameRecord::decode_name(&name));
+                    println!();
+                    continue;
+                }
+
+                // prompt the user for the fee theyd like to use
+                println!("Registering a name burns {:.8} BLO and registers it for {} blocks", *NAME_REGISTRATION_FEE as f64 / *LOWEST_DENOMINATION_PER_COIN, *NAME_REGISTRATION_PERIOD);
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                if NAME_REGISTRATION_FEE.saturating_add(fee) as f64 / *LOWEST_DENOMINATION_PER_COIN > controller.wallet_get_balance() {
+                    println!("Insufficient funds");
+                    println!();
+                    continue;
+                }
+
+                // create the transaction
+                let transaction = match controller.transaction_create_name_register(name, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "4" | "4." | "renew name" | "renew" => {
+                let name = match read_name() {
+                    Some(name) => name,
+                    None => continue
+                };
+
+                // only the owner of a name can renew it
+                match controller.blockchain_get_name_record(&name) {
+                    Some(name_record) if name_record.get_owner() == controller.wallet_get_address() => println!("{} expires at block height {}", NameRecord::decode_name(&name), name_record.get_expiry_height()),
+                    _ => {
+                        println!("You don't own {}", NameRecord::decode_name(&name));
+                        println!();
+                        continue;
+                    }
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Renewing a name burns {:.8} BLO and extends its registration by {} blocks", *NAME_REGISTRATION_FEE as f64 / *LOWEST_DENOMINATION_PER_COIN, *NAME_REGISTRATION_PERIOD);
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                if NAME_REGISTRATION_FEE.saturating_add(fee) as f64 / *LOWEST_DENOMINATION_PER_COIN > controller.wallet_get_balance() {
+                    println!("Insufficient funds");
+                    println!();
+                    continue;
+                }
+
+                // create the transaction
+                let transaction = match controller.transaction_create_name_renew(name, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "5" | "5." | "transfer name" | "transfer" => {
+                let name = match read_name() {
+                    Some(name) => name,
+                    None => continue
+                };
+
+                // only the owner of a name that hasn't expired can transfer it
+                if controller.blockchain_resolve_name(&NameRecord::decode_name(&name)) != Some(controller.wallet_get_address()) {
+                    println!("You don't own {} or it has expired", NameRecord::decode_name(&name));
+                    println!();
+                    continue;
+                }
+
+                // prompt the user for the address that will own the name
+                let recipient = match read_address(controller, "Enter the address the name will resolve to (case sensitive) or \"exit\":").await {
+                    Some(recipient) => recipient,
+                    None => continue
+                };
+
+                // prompt the user for the fee theyd like to use
+                println!("Current base fee: {:.8} BLO, transactions with a lower fee won't be added to a block", controller.blockchain_get_base_fee());
+                let fee = match read_blo_amount("Enter the fee you'd like to attach to your transaction (in BLO) or \"exit\":", true) {
+                    Some(fee) => fee,
+                    None => continue
+                };
+
+                // create the transaction
+                let transaction = match controller.transaction_create_name_transfer(name, recipient, fee) {
+                    Some(transaction) => transaction,
+                    None => {
+                        println!("Failed creating transaction, check wallet file location/permissions");
+                        println!();
+                        continue
+                    }
+                };
+
+                submit_transaction(controller, &transaction).await;
+            },
+            "6" | "6." | "back" => {
+                break;
+            },
+            _ => {}
+        }
+    }
+}
+
+async fn perform_network_options(controller: &mut Controller) {
+    let network_options = vec!["Network Options", "View Peers", "Ping Peer", "Add Peer", "Remove Peer", "Back"];
+
+    loop {
+        // present network options to user
+        print_options(&network_options);
+
+        // get user choice
+        let option_input = read_string().to_lowercase();
+        println!();
+
+        match option_input.as_str() {
+            "1" | "1." | "view" | "view peers" => {
+                println!("Peer list:");
+                println!("{:?}", controller.network_get_peers());
+                println!();
+            },
+            "2" | "2." | "ping" | "ping peer" => {
+                loop {
+                    // display a list of peers to the user and a user enter peer inforamtion option
+                    println!("Choice a peer from the list below or choose other to ping a peer not in the list or \"exit\"");
+                    for (index, peer) in controller.network_get_peers().iter().enumerate() {
+                        println!("{}. {:?}", index + 1, peer);
+                    }
+                    let total_peers = controller.network_get_peers().len();
+                    println!("{}. Enter peer information", total_peers + 1);
+                    let peer_input = read_string().to_lowercase();
+                    println!();
+
+                    if peer_input.to_lowercase() == "exit" {
+                        break;
+                    }
+
+                    // convert user input to an index
+                    let peer_selection = match peer_input.parse::<usize>() {
+                        Ok(fee) => fee,
+                        Err(_) => continue
+                    };
+
+                    if peer_selection > 0 && peer_selection < total_peers + 1 {
+                        // if user input is one of the peers in the peer list
+                        // ping the peer they selected
+                        let mut peer = controller.network_get_peers()[peer_selection - 1].clone();
+
+                        if !controller.network_ping_peer(&mut peer).await {
+                            println!("Unable to ping peer {}:{}", peer.get_ip(), peer.get_port());
+                            println!();
+                        } else {
+                            println!("Successfully pinged peer {}:{}", peer.get_ip(), peer.get_port());
+                            println!();
+                        }
+                        break;
+                    } else if peer_selection == total_peers + 1 {
+                        // if user chose to enter a specific peer's ipv4:port combo
+                        // prompt user to enter peer information
+                        loop {
+                            println!("Enter peer information in ipv4:port format or \"exit\"");
+                            let peer_information = read_string();
+                            println!();
+
+                            if peer_information.to_lowercase() == "exit" {
+                                break;
+                            }
+
+                            let mut peer = match Peer::new(peer_information.as_str()) {
+                                Some(peer) => peer,
+                                None => {
+                                    println!("Invalid ipv4:port entered");
+                                    println!();
+                                    continue;
+                                }
+                            };
+
+                            if !controller.network_ping_peer(&mut peer).await {
+                                println!("Unable to ping peer {}:{}", peer.get_ip(), peer.get_port());
+                                println!();
+                            } else {
+                                println!("Successfully pinged peer {}:{}", peer.get_ip(), peer.get_port());
+                                println!();
+                            }
+                            break;
+                        }
+                        break;
+                    } else {
+                        // if user entered invalid input
+                        continue;
+                    }
+                }
+            },
+            "3" | "3." | "add" | "add peer" => {
+                // prompt user to enter peer information
+                loop {
+                    println!("Enter peer information of the peer to add in ipv4:port format or \"exit\"");
+                    let peer_information = read_string();
+                    println!();
+
+                    if peer_information.to_lowercase() == "exit" {
+                        break;
+                    }
+
+                    let mut peer = match Peer::new(peer_information.as_str()) {
+                        Some(peer) => peer,
+                        None => {
+                            println!("Invalid ipv4:port entered");
+                            println!();
+                            continue;
+                        }
+                    };
+
+                    // ping peer before adding them to the peer list
                     if !controller.network_ping_peer(&mut peer).await {
                         println!("Unable to ping peer {}:{}, check your connection and try again", peer.get_ip(), peer.get_port());
                         println!();
@@ -932,7 +2325,7 @@ async fn perform_network_options(controller: &mut Controller) {
 }
 
 fn perform_about_options(controller: &Controller) {
-    let about_options = vec!["About options", "Node version", "Wallet config values", "Validator config values", "Network config values", "Back"];
+    let about_options = vec!["About options", "Node version", "Wallet config values", "Validator config values", "Network config values", "Blockchain config values", "Back"];
 
     loop {
         // present transaction options to user
@@ -962,7 +2355,12 @@ fn perform_about_options(controller: &Controller) {
                 println!("{:?}", controller.about_network_config());
                 println!();
             },
-            "5" | "5." | "back" => {
+            "5" | "5." | "blockchain config values" | "blockchain config" | "blockchain" => {
+                println!("Blockchain config values:");
+                println!("{:?}", controller.about_blockchain_config());
+                println!();
+            },
+            "6" | "6." | "back" => {
                 break;
             },
             _ => {}
@@ -970,6 +2368,514 @@ fn perform_about_options(controller: &Controller) {
     }
 }
 
+fn read_blo_amount(prompt: &str, allow_zero: bool) -> Option<u64> {
+    // prompt the user for an amount of BLO and convert it to bits, returns None if the user entered exit
+    loop {
+        println!("{}", prompt);
+        let amount_string = read_string();
+        println!();
+
+        // exit if they entered exit
+        if amount_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        // parse the amount
+        let amount = match amount_string.parse::<f64>() {
+            Ok(amount) => amount,
+            Err(_) => continue
+        };
+
+        // confirm the user has entered a valid number
+        if amount < 0.0 || (!allow_zero && amount == 0.0) {
+            println!("Enter an amount larger than 0");
+            println!();
+            continue;
+        }
+
+        // ensure the user has entered a prcision of 8 deicmal places or less
+        let amount_string_parts = amount_string.split('.').collect::<Vec<&str>>();
+        if amount_string_parts.len() == 2 && amount_string_parts[1].len() > 8 {
+            println!("Please enter an amount of BLO with a maximum of 8 decimal places (0.00000001 = 1 bit)");
+            println!();
+            continue;
+        }
+
+        return Some((amount * *LOWEST_DENOMINATION_PER_COIN).ceil() as u64)
+    }
+}
+
+async fn read_address(controller: &mut Controller, prompt: &str) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+    // prompt the user for a block address and check it, returns None if the user entered exit
+    loop {
+        println!("{}", prompt);
+        let address_string = read_string();
+        println!();
+
+        // exit if they entered exit
+        if address_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        let address_string = resolve_name(controller, address_string).await;
+
+        // attempt to convert the address string to an address
+        let address: [u8; BLOCK_ADDRESS_SIZE] = match address_string.as_bytes().try_into() {
+            Ok(address) => address,
+            Err(_) => {
+                println!("Invalid address");
+                println!();
+                continue;
+            }
+        };
+
+        // address checksum checking
+        if !controller.check_address_checksum(address) {
+            println!("Invalid address, please check for typos");
+            println!();
+            continue;
+        }
+
+        return Some(address)
+    }
+}
+
+fn read_name() -> Option<[u8; MAX_NAME_SIZE]> {
+    // prompt the user for a name, returns None if the user entered exit
+    loop {
+        println!("Enter the name or \"exit\":");
+        let name_string = read_string();
+        println!();
+
+        if name_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        match NameRecord::encode_name(&name_string) {
+            Some(name) => return Some(name),
+            None => {
+                println!("Invalid name, a name is {} to {} letters, digits and hyphens that doesn't start or end with a hyphen", MIN_NAME_SIZE, MAX_NAME_SIZE);
+                println!();
+            }
+        }
+    }
+}
+
+async fn resolve_name(controller: &mut Controller, address_string: String) -> String {
+    // a registered name can be entered instead of an address, it is replaced with the address it resolves to
+    let mut resolved_address = controller.blockchain_resolve_name(&address_string);
+
+    // when not running a local blockchain a name that isn't registered yet on this node may have been registered in a block that hasn't been synchronized, ask the peers
+    // addresses are longer than any name, so only names are looked up
+    if resolved_address.is_none() && !controller.network_get_local_blockchain() && NameRecord::encode_name(&address_string).is_some() {
+        for peer in controller.network_get_peers() {
+            if let Some(peer_resolved_address) = controller.network_resolve_name(&peer, &address_string).await {
+                if peer_resolved_address.is_some() {
+                    println!("Name resolved by peer {}:{}", peer.get_ip(), peer.get_port());
+                }
+                resolved_address = peer_resolved_address;
+                break;
+            }
+        }
+    }
+
+    match resolved_address {
+        Some(address) => {
+            println!("{} resolves to {}", address_string.to_lowercase(), String::from_utf8_lossy(&address));
+            println!();
+            String::from_utf8_lossy(&address).into_owned()
+        },
+        None => address_string
+    }
+}
+
+fn read_batch_outputs(controller: &Controller) -> Option<Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>> {
+    // prompt the user for a csv file of "address,amount" lines (amount in BLO), returns None if the user entered exit
+    loop {
+        println!("Enter the path of a csv file with one \"address,amount\" line per recipient (amount in BLO, at most {} recipients) or \"exit\":", MAX_BATCH_OUTPUTS);
+        let path_string = read_string();
+        println!();
+
+        if path_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        let csv = match std::fs::read_to_string(&path_string) {
+            Ok(csv) => csv,
+            Err(_) => {
+                println!("Unable to read file: {}", path_string);
+                println!();
+                continue;
+            }
+        };
+
+        match parse_batch_outputs(controller, &csv) {
+            Ok(outputs) => return Some(outputs),
+            Err(error) => {
+                println!("{}", error);
+                println!();
+            }
+        };
+    }
+}
+
+fn parse_batch_outputs(controller: &Controller, csv: &str) -> Result<Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>, String> {
+    let mut outputs: Vec<([u8; BLOCK_ADDRESS_SIZE], u64)> = vec![];
+
+    for (index, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        // skip blank lines and an optional header line
+        if line.is_empty() || (index == 0 && line.to_lowercase().starts_with("address")) {
+            continue;
+        }
+
+        let (address_string, amount_string) = match line.split_once(',') {
+            Some((address_string, amount_string)) => (address_string.trim(), amount_string.trim()),
+            None => return Err(format!("Line {} isn't an \"address,amount\" line", index + 1))
+        };
+
+        // confirm the address is a valid address or a registered name
+        let address: [u8; BLOCK_ADDRESS_SIZE] = match controller.blockchain_resolve_name(address_string) {
+            Some(address) => address,
+            None => match address_string.as_bytes().try_into() {
+                Ok(address) if controller.check_address_checksum(address) => address,
+                _ => return Err(format!("Invalid address on line {}: {}", index + 1, address_string))
+            }
+        };
+
+        // confirm the amount is larger than 0 with a precision of 8 decimal places or less
+        let amount = match amount_string.parse::<f64>() {
+            Ok(amount) if amount > 0.0 && amount_string.split('.').nth(1).map(|decimals| decimals.len() <= 8).unwrap_or(true) => (amount * *LOWEST_DENOMINATION_PER_COIN).ceil() as u64,
+            _ => return Err(format!("Invalid amount on line {}: {}, enter an amount larger than 0 with a maximum of 8 decimal places", index + 1, amount_string))
+        };
+
+        outputs.push((address, amount));
+    }
+
+    if outputs.is_empty() || outputs.len() > MAX_BATCH_OUTPUTS {
+        return Err(format!("A batch payment must have between 1 and {} recipients, the file has {}", MAX_BATCH_OUTPUTS, outputs.len()))
+    }
+
+    Ok(outputs)
+}
+
+fn read_validity_window(controller: &Controller) -> Option<(Option<TxLock>, Option<TxLock>)> {
+    // validity windows can only be used once the validity window deployment is active
+    if !controller.blockchain_is_deployment_active("validity_window") {
+        return Some((None, None))
+    }
+
+    println!("Current block height: {}", controller.blockchain_get_block_height());
+    println!();
+
+    let valid_after = read_tx_lock("Enter the earliest block your transaction can be added to as \"height <block height>\" or \"time <unix timestamp>\", leave blank for no limit or \"exit\":")?;
+    let valid_until = read_tx_lock("Enter the latest block your transaction can be added to before it expires as \"height <block height>\" or \"time <unix timestamp>\", leave blank for no limit or \"exit\":")?;
+
+    Some((valid_after, valid_until))
+}
+
+fn read_memo(controller: &Controller) -> Option<Vec<u8>> {
+    // memos can only be used once the memo deployment is active
+    if !controller.blockchain_is_deployment_active("memo") {
+        return Some(vec![])
+    }
+
+    loop {
+        // prompt the user for a memo, returns None if the user entered exit
+        println!("Enter a memo of at most {} bytes to attach to your transaction, leave blank for no memo or \"exit\":", MAX_MEMO_SIZE);
+        let memo_string = read_string();
+        println!();
+
+        if memo_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        if memo_string.len() > MAX_MEMO_SIZE {
+            println!("Memo is {} bytes, the maximum is {} bytes", memo_string.len(), MAX_MEMO_SIZE);
+            println!();
+            continue;
+        }
+
+        return Some(memo_string.into_bytes())
+    }
+}
+
+fn read_tx_lock(prompt: &str) -> Option<Option<TxLock>> {
+    // prompt the user for a block height or timestamp bound, returns None if the user entered exit
+    loop {
+        println!("{}", prompt);
+        let tx_lock_string = read_string().to_lowercase();
+        println!();
+
+        if tx_lock_string == "exit" {
+            return None
+        }
+
+        if tx_lock_string.is_empty() {
+            return Some(None)
+        }
+
+        let tx_lock_parts = tx_lock_string.split_whitespace().collect::<Vec<&str>>();
+        let tx_lock = match (tx_lock_parts.first(), tx_lock_parts.get(1).map(|value| value.parse::<u64>())) {
+            (Some(&"height"), Some(Ok(block_height))) if tx_lock_parts.len() == 2 => TxLock::BlockHeight(block_height),
+            (Some(&"time"), Some(Ok(timestamp))) if tx_lock_parts.len() == 2 => TxLock::Timestamp(timestamp),
+            _ => continue
+        };
+
+        return Some(Some(tx_lock))
+    }
+}
+
+fn read_hash(prompt: &str, allow_blank: bool) -> Option<Option<[u8; 32]>> {
+    // prompt the user for a 32 byte hash or secret in hex, returns None if the user entered exit
+    loop {
+        println!("{}", prompt);
+        let hash_string = read_string();
+        println!();
+
+        if hash_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        if allow_blank && hash_string.is_empty() {
+            return Some(None)
+        }
+
+        match decode_hex(&hash_string).and_then(|hash| hash.try_into().ok()) {
+            Some(hash) => return Some(Some(hash)),
+            None => {
+                println!("Invalid value, enter 32 bytes in hex");
+                println!();
+            }
+        }
+    }
+}
+
+fn read_htlc(controller: &Controller) -> Option<([u8; 32], Htlc)> {
+    // prompt the user for the lock id of an open hash time-locked transfer, returns None if the user entered exit
+    loop {
+        let lock_id = match read_hash("Enter the lock ID (the transaction ID of the lock transaction) in hex or \"exit\":", false) {
+            Some(Some(lock_id)) => lock_id,
+            _ => return None
+        };
+
+        match controller.blockchain_get_htlc(&lock_id) {
+            Some(htlc) => return Some((lock_id, htlc)),
+            None => {
+                println!("No open transfer with that lock ID, it may not be in a block yet or was already claimed or refunded");
+                println!();
+            }
+        }
+    }
+}
+
+fn read_payment_channel(controller: &Controller) -> Option<([u8; 32], PaymentChannel)> {
+    // prompt the user for the channel id of an open payment channel, returns None if the user entered exit
+    loop {
+        let channel_id = match read_hash("Enter the channel ID (the transaction ID of the open transaction) in hex or \"exit\":", false) {
+            Some(Some(channel_id)) => channel_id,
+            _ => return None
+        };
+
+        match controller.blockchain_get_payment_channel(&channel_id) {
+            Some(channel) => return Some((channel_id, channel)),
+            None => {
+                println!("No open channel with that channel ID, it may not be in a block yet or was already settled");
+                println!();
+            }
+        }
+    }
+}
+
+fn read_channel_update() -> Option<ChannelUpdate> {
+    // prompt the user for a hex encoded channel update, returns None if the user entered exit
+    loop {
+        println!("Enter the channel update in hex or \"exit\":");
+        let channel_update_string = read_string();
+        println!();
+
+        if channel_update_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        match decode_hex(&channel_update_string).and_then(|channel_update| ChannelUpdate::from(channel_update).ok()) {
+            Some(channel_update) => return Some(channel_update),
+            None => {
+                println!("Invalid channel update");
+                println!();
+            }
+        };
+    }
+}
+
+fn read_multisig_keys(controller: &Controller) -> Option<(u8, Vec<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>)> {
+    // prompt the user for the public keys and threshold of a multisig account, returns None if the user entered exit
+    let mut public_keys: Vec<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> = vec![];
+
+    println!("Your public key: {}", encode_hex(&controller.wallet_get_public_key()));
+    println!();
+
+    loop {
+        println!("Enter a public key of the multisig account in hex ({} of at most {} entered), leave blank when done or \"exit\":", public_keys.len(), MAX_MULTISIG_PUBLIC_KEYS);
+        let public_key_string = read_string();
+        println!();
+
+        if public_key_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        if public_key_string.is_empty() {
+            if public_keys.is_empty() {
+                println!("Enter at least one public key");
+                println!();
+                continue;
+            }
+            break;
+        }
+
+        let public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = match decode_hex(&public_key_string).and_then(|public_key| public_key.try_into().ok()) {
+            Some(public_key) => public_key,
+            None => {
+                println!("Invalid public key, enter a {} byte compressed public key in hex", COMPRESSED_PUBLIC_KEY_SIZE);
+                println!();
+                continue;
+            }
+        };
+
+        if public_keys.contains(&public_key) {
+            println!("Public key already entered");
+            println!();
+            continue;
+        }
+
+        public_keys.push(public_key);
+        if public_keys.len() == MAX_MULTISIG_PUBLIC_KEYS {
+            break;
+        }
+    }
+
+    loop {
+        println!("Enter the number of signatures required to send from the multisig account (1 - {}) or \"exit\":", public_keys.len());
+        let threshold_string = read_string();
+        println!();
+
+        if threshold_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        match threshold_string.parse::<u8>() {
+            Ok(threshold) if threshold >= 1 && threshold as usize <= public_keys.len() => return Some((threshold, public_keys)),
+            _ => continue
+        };
+    }
+}
+
+fn read_multisig_transaction() -> Option<Transaction> {
+    // prompt the user for a hex encoded multisig transaction, returns None if the user entered exit
+    loop {
+        println!("Enter the multisig transaction in hex or \"exit\":");
+        let transaction_string = read_string();
+        println!();
+
+        if transaction_string.to_lowercase() == "exit" {
+            return None
+        }
+
+        match decode_hex(&transaction_string).and_then(|transaction| Transaction::from(transaction).ok()) {
+            Some(transaction) if transaction.multisig.is_some() => return Some(transaction),
+            _ => {
+                println!("Invalid multisig transaction");
+                println!();
+            }
+        };
+    }
+}
+
+fn print_multisig_transaction(transaction: &Transaction) {
+    // display a multisig transaction so it can be passed on to the other key holders
+    if let Some(multisig) = &transaction.multisig {
+        println!("Signatures: {} of {} required", multisig.get_signatures().len(), multisig.get_threshold());
+        if multisig.is_complete() {
+            println!("The transaction has enough signatures and can be submitted");
+        } else {
+            println!("Send the transaction to the other key holders to sign");
+        }
+    }
+    println!("Multisig transaction: {}", encode_hex(&transaction.serialize_tx()));
+    println!();
+}
+
+async fn submit_transaction(controller: &mut Controller, transaction: &Transaction) {
+    // only transactions sent from the wallet's own account use the wallet's nonce, multisig transactions use the multisig account's nonce
+    let wallet_transaction = controller.blockchain_get_sender_address(transaction) == Some(controller.wallet_get_address());
+
+    // try adding transaction to mempool
+    if !controller.blockchain_add_transaction_mempool(transaction) {
+        println!("Failed adding to transaction to mempool, transaction may be invalid");
+        println!();
+        return
+    }
+
+    // if successful check if local blockchain or not
+    if !controller.network_get_local_blockchain() {
+        // broadcast transaction to peers
+        let successful_broadcasted_peers = controller.network_broadcast_transaction(transaction).await.unwrap_or_default();
+
+        if !successful_broadcasted_peers.is_empty() {
+            // increment wallet nonce
+            if wallet_transaction {
+                controller.wallet_increment_nonce();
+            }
+            println!("Successfully added transaction to mempool and broadcasted transaction to: {:?}", successful_broadcasted_peers);
+            println!("Transaction ID: {}", encode_hex(&transaction.serialize_hash_txid()));
+            println!();
+        } else {
+            // if unable to broadcast transaction remove transaction from local mempool to keep in sync with the network
+            controller.blockchain_remove_transaction_mempool(transaction);
+            println!("Unsuccessful broadcasting transaction to peers, please check your connection to your peers and try again");
+            println!();
+        }
+    } else {
+        // increment wallet nonce
+        if wallet_transaction {
+            controller.wallet_increment_nonce();
+        }
+        println!("Successfully added transaction to mempool");
+        println!("Transaction ID: {}", encode_hex(&transaction.serialize_hash_txid()));
+        println!();
+    }
+}
+
+async fn submit_replacement_transaction(controller: &mut Controller, transaction: &Transaction, replacement: &Transaction) {
+    // the replacement takes the pending transaction's place in the mempool, the wallet nonce was already used by the pending transaction
+    if !controller.blockchain_add_transaction_mempool(replacement) {
+        println!("Failed replacing transaction in mempool, transaction may be invalid");
+        println!();
+        return
+    }
+
+    // if successful check if local blockchain or not
+    if !controller.network_get_local_blockchain() {
+        // broadcast the replacement to peers so they replace the pending transaction too
+        let successful_broadcasted_peers = controller.network_broadcast_transaction(replacement).await.unwrap_or_default();
+
+        if successful_broadcasted_peers.is_empty() {
+            // if unable to broadcast the replacement put the pending transaction back to keep in sync with the network
+            controller.blockchain_remove_transaction_mempool(replacement);
+            controller.blockchain_add_transaction_mempool(transaction);
+            println!("Unsuccessful broadcasting transaction to peers, please check your connection to your peers and try again");
+            println!();
+            return
+        }
+
+        println!("Successfully replaced transaction in mempool and broadcasted transaction to: {:?}", successful_broadcasted_peers);
+    } else {
+        println!("Successfully replaced transaction in mempool");
+    }
+    println!("Transaction ID: {}", encode_hex(&replacement.serialize_hash_txid()));
+    println!();
+}
+
 fn print_options(options: &Vec<&str>) {
     // print an options array to the user
     for (index, option) in options.iter().enumerate() {
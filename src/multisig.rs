@@ -0,0 +1,87 @@
+use k256::ecdsa::Signature;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde_big_array::Array;
+use bincode::{Options, ErrorKind};
+
+use crate::wallet::Wallet;
+
+use crate::constants::{BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE};
+
+// The keys of an m of n multisig account and the signatures authorizing a transaction from it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multisig {
+    // number of signatures required to spend from the account
+    threshold: u8,
+    // public keys of the account sorted in ascending order, the account address is derived from the threshold and these keys
+    public_keys: Vec<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>,
+    // index of the signing public key and its signature over the transaction metadata, sorted by index
+    signatures: Vec<(u8, Signature)>,
+}
+
+impl Multisig {
+    pub fn new(threshold: u8, public_keys: Vec<[u8; COMPRESSED_PUBLIC_KEY_SIZE]>, signatures: Vec<(u8, Signature)>) -> Self {
+        Self {
+            threshold,
+            public_keys,
+            signatures,
+        }
+    }
+
+    pub fn from(raw: Vec<u8>) -> Result<Self, Box<ErrorKind>> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .deserialize(&raw)
+    }
+
+    pub fn serialize_multisig(&self) -> Vec<u8> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize(self).unwrap()
+    }
+
+    pub fn get_threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub fn get_public_keys(&self) -> Vec<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> {
+        self.public_keys.clone()
+    }
+
+    pub fn get_signatures(&self) -> Vec<(u8, Signature)> {
+        self.signatures.clone()
+    }
+
+    pub fn get_address(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        Wallet::generate_multisig_address(self.threshold, &self.public_keys)
+    }
+
+    pub fn add_signature(&mut self, index: u8, signature: Signature) {
+        // replace any earlier signature from the same key and keep the signatures sorted by index
+        self.signatures.retain(|(signature_index, _)| *signature_index != index);
+        self.signatures.push((index, signature));
+        self.signatures.sort_by_key(|(signature_index, _)| *signature_index);
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.signatures.len() >= self.threshold as usize
+    }
+}
+
+// public keys are larger than the arrays serde supports, serialize them with serde_big_array
+impl Serialize for Multisig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let public_keys: Vec<Array<u8, COMPRESSED_PUBLIC_KEY_SIZE>> = self.public_keys.iter().map(|public_key| Array(*public_key)).collect();
+        (self.threshold, public_keys, &self.signatures).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Multisig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (threshold, public_keys, signatures): (u8, Vec<Array<u8, COMPRESSED_PUBLIC_KEY_SIZE>>, Vec<(u8, Signature)>) = Deserialize::deserialize(deserializer)?;
+        Ok(Multisig::new(threshold, public_keys.into_iter().map(|public_key| public_key.0).collect(), signatures))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use crate::constants::{BLOCK_ADDRESS_SIZE, MAX_NAME_SIZE, MIN_NAME_SIZE};
+
+// A registered name and the address it resolves to until it expires
+#[derive(Debug, Clone)]
+pub struct NameRecord {
+    // address of the account that owns the name, the name resolves to this address
+    owner: [u8; BLOCK_ADDRESS_SIZE],
+    // block height from which the name no longer resolves and anyone can register it
+    expiry_height: u64,
+}
+
+impl NameRecord {
+    pub fn new(owner: [u8; BLOCK_ADDRESS_SIZE], expiry_height: u64) -> Self {
+        Self {
+            owner,
+            expiry_height,
+        }
+    }
+
+    pub fn get_owner(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        self.owner
+    }
+
+    pub fn set_owner(&mut self, owner: [u8; BLOCK_ADDRESS_SIZE]) {
+        self.owner = owner
+    }
+
+    pub fn get_expiry_height(&self) -> u64 {
+        self.expiry_height
+    }
+
+    pub fn set_expiry_height(&mut self, expiry_height: u64) {
+        self.expiry_height = expiry_height
+    }
+
+    pub fn is_expired(&self, block_height: u64) -> bool {
+        block_height >= self.expiry_height
+    }
+
+    pub fn encode_name(name: &str) -> Option<[u8; MAX_NAME_SIZE]> {
+        // names aren't case sensitive, they are stored in lowercase and padded with zeros
+        let name = name.to_lowercase();
+        if name.len() > MAX_NAME_SIZE {
+            return None
+        }
+
+        let mut encoded_name = [0x00; MAX_NAME_SIZE];
+        encoded_name[..name.len()].copy_from_slice(name.as_bytes());
+
+        if !Self::is_valid_name(&encoded_name) {
+            return None
+        }
+
+        Some(encoded_name)
+    }
+
+    pub fn decode_name(name: &[u8; MAX_NAME_SIZE]) -> String {
+        String::from_utf8_lossy(&name[..Self::get_name_size(name)]).into_owned()
+    }
+
+    pub fn is_valid_name(name: &[u8; MAX_NAME_SIZE]) -> bool {
+        // a valid name is MIN_NAME_SIZE to MAX_NAME_SIZE lowercase letters, digits and hyphens that doesn't start or end with a hyphen, followed only by zeros
+        let name_size = Self::get_name_size(name);
+        if name_size < MIN_NAME_SIZE || name[name_size..].iter().any(|byte| *byte != 0x00) {
+            return false
+        }
+
+        if name[0] == b'-' || name[name_size - 1] == b'-' {
+            return false
+        }
+
+        name[..name_size].iter().all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || *byte == b'-')
+    }
+
+    fn get_name_size(name: &[u8; MAX_NAME_SIZE]) -> usize {
+        name.iter().position(|byte| *byte == 0x00).unwrap_or(MAX_NAME_SIZE)
+    }
+}
@@ -1,13 +1,16 @@
 use std::{net::Ipv4Addr, str::FromStr};
 
-use k256::ecdsa::Signature;
+use k256::ecdsa;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::attestation::Attestation;
 use crate::block::{Block, BlockHeader};
 use crate::config::NetworkConfig;
-use crate::constants::{API_VERSION, BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE, NODE_VERSION};
-use crate::transaction::Transaction;
+use crate::multisig::Multisig;
+use crate::signature::Signature;
+use crate::constants::{API_VERSION, BATCH_TRANSACTION_VERSION, BLOCK_ADDRESS_SIZE, BLOCK_FORMAT_VERSION_MASK, COMPRESSED_PUBLIC_KEY_SIZE, DATA_TRANSACTION_VERSION, MEMO_TRANSACTION_VERSION, MULTISIG_TRANSACTION_VERSION, NODE_VERSION, VALIDITY_WINDOW_TRANSACTION_VERSION, VRF_BLOCK_VERSION, VRF_PROOF_SIZE};
+use crate::transaction::{Transaction, TxData, TxLock};
 
 use protoping::ping_service_client::PingServiceClient;
 use protoping::BroadcastPingRequest;
@@ -16,7 +19,13 @@ use prototransaction::transaction_service_client::TransactionServiceClient;
 use prototransaction::BroadcastTransactionRequest;
 
 use protoblock::block_service_client::BlockServiceClient;
-use protoblock::{BroadcastBlockRequest, GetBlockRequest, GetBlockHeightRequest};
+use protoblock::{BroadcastBlockRequest, GetBlockRequest, GetBlockHeightRequest, GetProposerScheduleRequest};
+
+use protoattestation::attestation_service_client::AttestationServiceClient;
+use protoattestation::{BroadcastAttestationRequest, GetAttestationsRequest};
+
+use protoname::name_service_client::NameServiceClient;
+use protoname::{GetNamesRequest, ResolveNameRequest};
 
 pub mod protoping {
     tonic::include_proto!("block.ping");
@@ -30,6 +39,14 @@ pub mod protoblock {
     tonic::include_proto!("block.block");
 }
 
+pub mod protoattestation {
+    tonic::include_proto!("block.attestation");
+}
+
+pub mod protoname {
+    tonic::include_proto!("block.name");
+}
+
 pub struct Network {
     // config for the network
     config: NetworkConfig,
@@ -223,6 +240,13 @@ impl Network {
                         sender: transaction.sender.to_vec(),
                         signature: transaction.signature.to_vec(),
                         nonce: transaction.nonce,
+                        data: transaction.data.serialize_tx_data(),
+                        multisig: transaction.multisig.as_ref().map(|multisig| multisig.serialize_multisig()).unwrap_or_default(),
+                        valid_after: transaction.valid_after.map(|valid_after| valid_after.serialize_tx_lock()).unwrap_or_default(),
+                        valid_until: transaction.valid_until.map(|valid_until| valid_until.serialize_tx_lock()).unwrap_or_default(),
+                        memo: transaction.memo.clone(),
+                        outputs: transaction.outputs.iter().map(|(recipient, amount)| prototransaction::TxOutput { recipient: recipient.to_vec(), amount: *amount }).collect(),
+                        signature_scheme: transaction.signature.get_scheme().into(),
                     })
                 });
 
@@ -275,6 +299,7 @@ impl Network {
                             prev_hash: block.prev_hash().to_vec(),
                             merkle_root: block.merkle_root().to_vec(),
                             timestamp: block.get_timesamp(),
+                            vrf_proof: block.get_vrf_proof().to_vec(),
                         }),
                         transactions: block.get_transactions().iter().map(|transaction| protoblock::Transaction {
                             version: transaction.version.into(),
@@ -284,8 +309,16 @@ impl Network {
                             sender: transaction.sender.to_vec(),
                             signature: transaction.signature.to_vec(),
                             nonce: transaction.nonce,
+                            data: transaction.data.serialize_tx_data(),
+                            multisig: transaction.multisig.as_ref().map(|multisig| multisig.serialize_multisig()).unwrap_or_default(),
+                            valid_after: transaction.valid_after.map(|valid_after| valid_after.serialize_tx_lock()).unwrap_or_default(),
+                            valid_until: transaction.valid_until.map(|valid_until| valid_until.serialize_tx_lock()).unwrap_or_default(),
+                            memo: transaction.memo.clone(),
+                            outputs: transaction.outputs.iter().map(|(recipient, amount)| protoblock::TxOutput { recipient: recipient.to_vec(), amount: *amount }).collect(),
+                            signature_scheme: transaction.signature.get_scheme().into(),
                         }).collect(),
                         signature: block.get_signature().to_vec(),
+                        signature_scheme: block.get_signature().get_scheme().into(),
                     })
                 });
 
@@ -387,10 +420,10 @@ impl Network {
 
             // obtain the block size
             let block_size = protoblock.block_size;
-            // obtain the signature
-            let signature = match Signature::from_slice(&protoblock.signature) {
-                Ok(signature) => signature,
-                Err(_) => {
+            // obtain the signature and its signature scheme
+            let signature = match u8::try_from(protoblock.signature_scheme).ok().and_then(|signature_scheme| Signature::from_parts(signature_scheme, &protoblock.signature)) {
+                Some(signature) => signature,
+                None => {
                     println!("Improperly formatted signature from peer {}:{} in block at height {}", peer.ip, peer.port, block_height);
                     println!();
                     return None
@@ -423,8 +456,21 @@ impl Network {
                     return None
                 },
             };
+            // obtain the vrf proof from the protoblock::blockheader, only blocks of format VRF_BLOCK_VERSION or later carry a vrf proof
+            let vrf_proof: [u8; VRF_PROOF_SIZE] = if proto_block_header.version & *BLOCK_FORMAT_VERSION_MASK >= *VRF_BLOCK_VERSION {
+                match proto_block_header.vrf_proof.try_into() {
+                    Ok(vrf_proof) => vrf_proof,
+                    Err(_) => {
+                        println!("Failed obtaining vrf proof from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                        println!();
+                        return None
+                    },
+                }
+            } else {
+                [0x00; VRF_PROOF_SIZE]
+            };
             // build the block::blockheader
-            let block_header = BlockHeader::new(proto_block_header.version, prev_hash, merkle_root, proto_block_header.timestamp);
+            let block_header = BlockHeader::new(proto_block_header.version, prev_hash, merkle_root, proto_block_header.timestamp, vrf_proof);
             // convert the protoblock::transactions to block::transactions need to cast u32 -> u8 here, need to be careful
             let mut transactions = vec![];
             for transaction in protoblock.transactions.iter() {
@@ -459,10 +505,10 @@ impl Network {
                         return None
                     }
                 };
-                // obtain the transaction signature
-                let tx_signature = match Signature::from_slice(&transaction.signature) {
-                    Ok(tx_signature) => tx_signature,
-                    Err(_) => {
+                // obtain the transaction signature and its signature scheme
+                let tx_signature = match u8::try_from(transaction.signature_scheme).ok().and_then(|signature_scheme| Signature::from_parts(signature_scheme, &transaction.signature)) {
+                    Some(tx_signature) => tx_signature,
+                    None => {
                         println!("Improperly formatted signature in transaction from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
                         println!();
                         return None
@@ -470,9 +516,77 @@ impl Network {
                 };
                 // obtain the nonce
                 let nonce = transaction.nonce;
+                // obtain the transaction data, only transactions of DATA_TRANSACTION_VERSION or later carry data
+                let data = if version >= *DATA_TRANSACTION_VERSION {
+                    match TxData::from(transaction.data.clone()) {
+                        Ok(data) => data,
+                        Err(_) => {
+                            println!("Improperly formatted data in transaction from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                            println!();
+                            return None
+                        }
+                    }
+                } else {
+                    TxData::None
+                };
+                // obtain the multisig keys and signatures, only transactions of MULTISIG_TRANSACTION_VERSION or later from multisig accounts carry them
+                let multisig = if version >= *MULTISIG_TRANSACTION_VERSION && !transaction.multisig.is_empty() {
+                    match Multisig::from(transaction.multisig.clone()) {
+                        Ok(multisig) => Some(multisig),
+                        Err(_) => {
+                            println!("Improperly formatted multisig in transaction from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                            println!();
+                            return None
+                        }
+                    }
+                } else {
+                    None
+                };
+                // obtain the validity window, only transactions of VALIDITY_WINDOW_TRANSACTION_VERSION or later carry one
+                let mut validity_window = vec![];
+                for tx_lock in [&transaction.valid_after, &transaction.valid_until] {
+                    if version >= *VALIDITY_WINDOW_TRANSACTION_VERSION && !tx_lock.is_empty() {
+                        match TxLock::from(tx_lock.clone()) {
+                            Ok(tx_lock) => validity_window.push(Some(tx_lock)),
+                            Err(_) => {
+                                println!("Improperly formatted validity window in transaction from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                                println!();
+                                return None
+                            }
+                        }
+                    } else {
+                        validity_window.push(None);
+                    }
+                }
+
+                // obtain the memo, only transactions of MEMO_TRANSACTION_VERSION or later carry one
+                let memo = if version >= *MEMO_TRANSACTION_VERSION {
+                    transaction.memo.clone()
+                } else {
+                    vec![]
+                };
+
+                // obtain the batch outputs, only transactions of BATCH_TRANSACTION_VERSION or later carry them
+                let mut outputs = vec![];
+                if version >= *BATCH_TRANSACTION_VERSION {
+                    for output in transaction.outputs.iter() {
+                        match output.recipient.clone().try_into() {
+                            Ok(output_recipient) => outputs.push((output_recipient, output.amount)),
+                            Err(_) => {
+                                println!("Failed obtaining output recipient in transaction from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                                println!();
+                                return None
+                            }
+                        }
+                    }
+                }
 
                 // obtain the amount
-                transactions.push(Transaction::new(version, amount, fee, recipient, sender, tx_signature, nonce));
+                let transaction = Transaction::new(version, amount, fee, recipient, sender, tx_signature, nonce).with_data(data).with_validity_window(validity_window[0], validity_window[1]).with_memo(memo).with_outputs(outputs);
+                transactions.push(match multisig {
+                    Some(multisig) => transaction.with_multisig(multisig),
+                    None => transaction
+                });
             }
 
             return Some(Block::from_parts(block_size, block_header, transactions, signature))
@@ -481,6 +595,252 @@ impl Network {
         }
     }
 
+    pub async fn get_proposer_schedule(&mut self, peer: &Peer, public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE]) -> Option<(Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)>, f64)> {
+        // only attempt to get the proposer schedule if not running a local blockchain
+        if !self.get_local_blockchain() {
+            // attempt to establish connection with the peer
+            let mut client = match BlockServiceClient::connect(format!("http://{}:{}", peer.ip, peer.port)).await {
+                Ok(client) => client,
+                Err(_) => {
+                    println!("Unable to connect to peer: {}:{} ", peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            // create the request, the public key is the key the peer reports the proposer probability for
+            let request = tonic::Request::new(GetProposerScheduleRequest {
+                public_key: public_key.to_vec(),
+            });
+
+            // make the request to the peer and get a response
+            let response = match client.get_proposer_schedule(request).await {
+                Ok(response) => response.into_inner(),
+                Err(_) => {
+                    println!("Unable to get proposer schedule from peer: {}:{}", peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            // convert the protoblock::proposerslots to (public key, earliest timestamp) tuples
+            let mut schedule = vec![];
+            for proposer in response.proposers.iter() {
+                let proposer_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = match proposer.public_key.clone().try_into() {
+                    Ok(proposer_pub_key) => proposer_pub_key,
+                    Err(_) => {
+                        println!("Failed obtaining proposer public key from peer {}:{}", peer.ip, peer.port);
+                        println!();
+                        return None
+                    }
+                };
+
+                schedule.push((proposer_pub_key, proposer.earliest_timestamp));
+            }
+
+            Some((schedule, response.probability))
+        } else {
+            None
+        }
+    }
+
+    pub async fn broadcast_attestation(&mut self, attestation: &Attestation) -> Option<Vec<Peer>> {
+        let mut successful_broadcasts = vec![];
+
+        // only attempt to broadcast attestation if not running a local blockchain
+        if !self.get_local_blockchain() {
+            for peer in self.peer_list.iter_mut() { 
+                // attempt to establish connection with the peer
+                let mut client = match AttestationServiceClient::connect(format!("http://{}:{}", peer.ip, peer.port)).await {
+                    Ok(client) => client,
+                    Err(_) => {
+                        println!("Unable to connect to peer: {}:{} ", peer.ip, peer.port);
+                        println!();
+                        continue
+                    }
+                };
+
+                // create the request
+                let request = tonic::Request::new(BroadcastAttestationRequest {
+                    attestation: Some(protoattestation::Attestation {
+                        block_height: attestation.get_block_height(),
+                        block_hash: attestation.get_block_hash().to_vec(),
+                        public_key: attestation.get_public_key().to_vec(),
+                        signature: attestation.get_signature().to_vec(),
+                    })
+                });
+
+                // make the request to the peer and get a response
+                let response = match client.broadcast_attestation(request).await {
+                    Ok(response) => response.into_inner(),
+                    Err(_) => {
+                        println!("Unable to broadcast attestation to peer: {}:{}", peer.ip, peer.port);
+                        println!();
+                        continue
+                    }
+                };
+
+                // parse attestation broadcast response
+                if !response.ok {
+                    println!("Broadcast attestation rejection from peer: {}:{}", peer.ip, peer.port);
+                    println!();
+                    continue
+                } 
+
+                successful_broadcasts.push(peer.to_owned());
+            }
+        }
+
+        Some(successful_broadcasts)
+    }
+
+    pub async fn get_attestations(&mut self, peer: &Peer, block_height: u64) -> Option<Vec<Attestation>> {
+        // only attempt to get attestations if not running a local blockchain
+        if !self.get_local_blockchain() {
+            // attempt to establish connection with the peer
+            let mut client = match AttestationServiceClient::connect(format!("http://{}:{}", peer.ip, peer.port)).await {
+                Ok(client) => client,
+                Err(_) => {
+                    println!("Unable to connect to peer: {}:{} ", peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            // create the request
+            let request = tonic::Request::new(GetAttestationsRequest {
+                block_height
+            });
+
+            // make the request to the peer and get a response
+            let response = match client.get_attestations(request).await {
+                Ok(response) => response.into_inner(),
+                Err(_) => {
+                    println!("Unable to obtain attestations at height {} from peer: {}:{}", block_height, peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            // convert the protoattestation::attestations to attestation::attestations
+            let mut attestations = vec![];
+            for attestation in response.attestations.iter() {
+                // obtain the block hash
+                let block_hash: [u8; 32] = match attestation.block_hash.clone().try_into() {
+                    Ok(block_hash) => block_hash,
+                    Err(_) => {
+                        println!("Failed obtaining block hash in attestation from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                        println!();
+                        return None
+                    }
+                };
+                // obtain the public key
+                let public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = match attestation.public_key.clone().try_into() {
+                    Ok(public_key) => public_key,
+                    Err(_) => {
+                        println!("Failed obtaining public key in attestation from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                        println!();
+                        return None
+                    }
+                };
+                // obtain the signature
+                let signature = match ecdsa::Signature::from_slice(&attestation.signature) {
+                    Ok(signature) => signature,
+                    Err(_) => {
+                        println!("Improperly formatted signature in attestation from peer {}:{} at block height {}", peer.ip, peer.port, block_height);
+                        println!();
+                        return None
+                    }
+                };
+
+                attestations.push(Attestation::new(attestation.block_height, block_hash, public_key, signature));
+            }
+
+            Some(attestations)
+        } else {
+            None
+        }
+    }
+
+    pub async fn resolve_name(&mut self, peer: &Peer, name: &str) -> Option<Option<[u8; BLOCK_ADDRESS_SIZE]>> {
+        // only attempt to resolve the name if not running a local blockchain
+        if !self.get_local_blockchain() {
+            // attempt to establish connection with the peer
+            let mut client = match NameServiceClient::connect(format!("http://{}:{}", peer.ip, peer.port)).await {
+                Ok(client) => client,
+                Err(_) => {
+                    println!("Unable to connect to peer: {}:{} ", peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            // create the request
+            let request = tonic::Request::new(ResolveNameRequest {
+                name: name.to_string(),
+            });
+
+            // make the request to the peer and get a response
+            let response = match client.resolve_name(request).await {
+                Ok(response) => response.into_inner(),
+                Err(_) => {
+                    println!("Unable to resolve name {} with peer: {}:{}", name, peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            // the peer doesn't return an address if the name isn't registered or has expired
+            match response.address {
+                Some(address) => match address.try_into() {
+                    Ok(address) => Some(Some(address)),
+                    Err(_) => {
+                        println!("Failed obtaining address of name {} from peer {}:{}", name, peer.ip, peer.port);
+                        println!();
+                        None
+                    }
+                },
+                None => Some(None)
+            }
+        } else {
+            None
+        }
+    }
+
+    pub async fn get_names(&mut self, peer: &Peer, address: &[u8; BLOCK_ADDRESS_SIZE]) -> Option<Vec<String>> {
+        // only attempt to get the names of the address if not running a local blockchain
+        if !self.get_local_blockchain() {
+            // attempt to establish connection with the peer
+            let mut client = match NameServiceClient::connect(format!("http://{}:{}", peer.ip, peer.port)).await {
+                Ok(client) => client,
+                Err(_) => {
+                    println!("Unable to connect to peer: {}:{} ", peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            // create the request
+            let request = tonic::Request::new(GetNamesRequest {
+                address: address.to_vec(),
+            });
+
+            // make the request to the peer and get a response
+            let response = match client.get_names(request).await {
+                Ok(response) => response.into_inner(),
+                Err(_) => {
+                    println!("Unable to obtain names of {} from peer: {}:{}", String::from_utf8_lossy(address), peer.ip, peer.port);
+                    println!();
+                    return None
+                }
+            };
+
+            Some(response.names)
+        } else {
+            None
+        }
+    }
+
     pub fn add_peer(&mut self, peer: &Peer) {
         // make sure the peer is not already in the peer_list
         for current_peer in &self.peer_list {
@@ -0,0 +1,60 @@
+use crate::constants::{BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE};
+
+// Funds deposited by a payer into a unidirectional payment channel, paid out to the payee off-chain with channel updates
+#[derive(Debug, Clone)]
+pub struct PaymentChannel {
+    // public key the payer opened the channel with, updates are signed with the payer account's rotated key instead if it has one
+    payer_public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE],
+    // address of the account that opened the channel, it gets back whatever isn't paid to the payee
+    payer: [u8; BLOCK_ADDRESS_SIZE],
+    // address of the account the channel pays
+    payee: [u8; BLOCK_ADDRESS_SIZE],
+    // amount of funds deposited into the channel
+    deposit: u64,
+    // amount paid to the payee by the update the payer closed the channel with
+    paid: u64,
+    // block height at which a channel closed by the payer is settled, unless the payee closes it with its own update first
+    settle_height: Option<u64>,
+}
+
+impl PaymentChannel {
+    pub fn new(payer_public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE], payer: [u8; BLOCK_ADDRESS_SIZE], payee: [u8; BLOCK_ADDRESS_SIZE], deposit: u64) -> Self {
+        Self {
+            payer_public_key,
+            payer,
+            payee,
+            deposit,
+            paid: 0,
+            settle_height: None,
+        }
+    }
+
+    pub fn get_payer_public_key(&self) -> [u8; COMPRESSED_PUBLIC_KEY_SIZE] {
+        self.payer_public_key
+    }
+
+    pub fn get_payer(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        self.payer
+    }
+
+    pub fn get_payee(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        self.payee
+    }
+
+    pub fn get_deposit(&self) -> u64 {
+        self.deposit
+    }
+
+    pub fn get_paid(&self) -> u64 {
+        self.paid
+    }
+
+    pub fn get_settle_height(&self) -> Option<u64> {
+        self.settle_height
+    }
+
+    pub fn close(&mut self, paid: u64, settle_height: u64) {
+        self.paid = paid;
+        self.settle_height = Some(settle_height);
+    }
+}
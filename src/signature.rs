@@ -0,0 +1,81 @@
+use std::fmt;
+
+use k256::{ecdsa, schnorr};
+use serde::{Serialize, Serializer, Deserialize, Deserializer, ser::SerializeTuple, de::{self, Visitor, SeqAccess}};
+use serde_big_array::Array;
+
+use crate::constants::{ECDSA_SIGNATURE_SCHEME, SCHNORR_SIGNATURE_SCHEME, SIGNATURE_SIZE};
+
+// A transaction or block signature, every version can be signed with ECDSA and versions from SCHNORR_TRANSACTION_VERSION and SCHNORR_BLOCK_VERSION with Schnorr too
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signature {
+    // secp256k1 ECDSA signature, only valid with a low S value
+    Ecdsa(ecdsa::Signature),
+    // BIP340 Schnorr signature, verified with the x coordinate of the signer's public key so addresses stay the same
+    Schnorr(schnorr::Signature),
+}
+
+impl Signature {
+    pub fn from_parts(scheme: u8, bytes: &[u8]) -> Option<Self> {
+        match scheme {
+            scheme if scheme == *ECDSA_SIGNATURE_SCHEME => ecdsa::Signature::from_slice(bytes).ok().map(Signature::Ecdsa),
+            scheme if scheme == *SCHNORR_SIGNATURE_SCHEME => schnorr::Signature::try_from(bytes).ok().map(Signature::Schnorr),
+            _ => None
+        }
+    }
+
+    pub fn get_scheme(&self) -> u8 {
+        match self {
+            Signature::Ecdsa(_) => *ECDSA_SIGNATURE_SCHEME,
+            Signature::Schnorr(_) => *SCHNORR_SIGNATURE_SCHEME,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_SIZE] {
+        match self {
+            Signature::Ecdsa(signature) => signature.to_bytes().into(),
+            Signature::Schnorr(signature) => signature.to_bytes(),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+
+    pub fn is_ecdsa(&self) -> bool {
+        matches!(self, Signature::Ecdsa(_))
+    }
+}
+
+// signatures are serialized as the scheme followed by the signature bytes, versions from before Schnorr signatures only serialize the bytes of their ECDSA signature
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.get_scheme())?;
+        tuple.serialize_element(&Array(self.to_bytes()))?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SignatureVisitor;
+
+        impl<'de> Visitor<'de> for SignatureVisitor {
+            type Value = Signature;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a serialized signature")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Signature, A::Error> {
+                let scheme: u8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let bytes: Array<u8, SIGNATURE_SIZE> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+                Signature::from_parts(scheme, &bytes.0).ok_or_else(|| de::Error::custom("invalid signature"))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, SignatureVisitor)
+    }
+}
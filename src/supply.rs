@@ -0,0 +1,58 @@
+// Breakdown of the total supply after the block at block_height was added
+#[derive(Debug, Clone)]
+pub struct Supply {
+    // block height the supply was recorded at
+    block_height: u64,
+    // total amount ever issued by coinbase transactions, not counting recycled loose change
+    issued: u64,
+    // amount staked by validators and delegators, held by the VALIDATOR_ENABLE_RECIPIENT
+    staked: u64,
+    // amount of revoked stake still waiting in the unbonding queue
+    unbonding: u64,
+    // amount burned by base fees and validator enable fees, held by the BURN_RECIPIENT
+    burned: u64,
+    // amount lost to the loose change pool that hasn't been recycled yet, held by the LOOSE_CHANGE_RECIPIENT
+    lost: u64,
+}
+
+impl Supply {
+    pub fn new(block_height: u64, issued: u64, staked: u64, unbonding: u64, burned: u64, lost: u64) -> Self {
+        Self {
+            block_height,
+            issued,
+            staked,
+            unbonding,
+            burned,
+            lost,
+        }
+    }
+
+    pub fn get_block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    pub fn get_issued(&self) -> u64 {
+        self.issued
+    }
+
+    pub fn get_circulating(&self) -> u64 {
+        // everything issued that can currently be spent by an account
+        self.issued - self.staked - self.unbonding - self.burned - self.lost
+    }
+
+    pub fn get_staked(&self) -> u64 {
+        self.staked
+    }
+
+    pub fn get_unbonding(&self) -> u64 {
+        self.unbonding
+    }
+
+    pub fn get_burned(&self) -> u64 {
+        self.burned
+    }
+
+    pub fn get_lost(&self) -> u64 {
+        self.lost
+    }
+}
\ No newline at end of file
@@ -1,13 +1,21 @@
 use std::cmp::Ordering;
+use std::fmt;
 
-use k256::ecdsa::Signature;
-use serde::{Serialize, Deserialize};
+use k256::ecdsa;
+use serde::{Serialize, Serializer, Deserialize, Deserializer, ser::SerializeTuple, de::{self, Visitor, SeqAccess}};
+use serde_big_array::{Array, BigArray};
 use sha2::{Sha256, Digest};
 use bincode::{Options, ErrorKind};
+use k256::PublicKey;
 
-use crate::constants::{BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE};
+use crate::channel_update::ChannelUpdate;
+use crate::multisig::Multisig;
+use crate::signature::Signature;
+use crate::wallet::Wallet;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use crate::constants::{BATCH_TRANSACTION_VERSION, BLOCK_ADDRESS_SIZE, CHAIN_ID_TRANSACTION_VERSION, COMPRESSED_PUBLIC_KEY_SIZE, DATA_TRANSACTION_VERSION, MAX_NAME_SIZE, MEMO_TRANSACTION_VERSION, MULTISIG_TRANSACTION_VERSION, SCHNORR_TRANSACTION_VERSION, VALIDITY_WINDOW_TRANSACTION_VERSION};
+
+#[derive(Debug, Clone)]
 pub struct Transaction {
     // transaction version
     pub version: u8,
@@ -16,15 +24,102 @@ pub struct Transaction {
     // fee in smallest denomination - 0.00000001
     pub fee: u64,
     // recipeint in address format (base58encoded(version bytes + pubkey + checksum))
-    #[serde(with = "serde_big_array::BigArray")]
     pub recipient: [u8; BLOCK_ADDRESS_SIZE],
-    // sender compressed public key 0x02 or 0x03 (if y is even/odd respesctively) + x point
-    #[serde(with = "serde_big_array::BigArray")]
+    // sender compressed public key 0x02 or 0x03 (if y is even/odd respesctively) + x point, MULTISIG_SENDER for multisig accounts
     pub sender: [u8; COMPRESSED_PUBLIC_KEY_SIZE],
-    // sign(sha256(version + amount + fee + recipient + nonce))
+    // sign(sha256(version + amount + fee + recipient + nonce)), the first multisig signature for multisig accounts, an ECDSA signature must have a low S value
+    // only serialized with its signature scheme for transaction versions >= SCHNORR_TRANSACTION_VERSION, earlier versions can only be signed with ECDSA
     pub signature: Signature,
     // account nonce, incremented once for each confirmed transaction
     pub nonce: u64,
+    // type specific transaction data, only serialized for transaction versions >= DATA_TRANSACTION_VERSION
+    pub data: TxData,
+    // keys and signatures of the sending multisig account, only serialized for transaction versions >= MULTISIG_TRANSACTION_VERSION
+    pub multisig: Option<Multisig>,
+    // earliest block the transaction can be added to, only serialized for transaction versions >= VALIDITY_WINDOW_TRANSACTION_VERSION
+    pub valid_after: Option<TxLock>,
+    // latest block the transaction can be added to, after which it expires, only serialized for transaction versions >= VALIDITY_WINDOW_TRANSACTION_VERSION
+    pub valid_until: Option<TxLock>,
+    // free-form payment reference of at most MAX_MEMO_SIZE bytes, empty for no memo, only serialized for transaction versions >= MEMO_TRANSACTION_VERSION
+    pub memo: Vec<u8>,
+    // recipients and amounts paid by a batch transaction, empty for every other transaction, only serialized for transaction versions >= BATCH_TRANSACTION_VERSION
+    pub outputs: Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>,
+}
+
+// type specific data carried by a transaction, the recipient and amount of the transaction are interpreted based on this
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TxData {
+    // no additional data, the transaction type is determined by the recipient
+    None,
+    // delegate amount to the validator at recipient
+    Delegate,
+    // undelegate amount from the validator at recipient
+    Undelegate,
+    // set the sending validator's commission in basis points
+    ValidatorCommission(u16),
+    // lock amount for the recipient until the preimage of hash_lock is revealed, the sender can take it back from refund_height
+    HtlcLock { hash_lock: [u8; 32], refund_height: u64 },
+    // claim the hash time-locked transfer created by the lock transaction with id lock_id by revealing the preimage of its hash lock
+    HtlcClaim { lock_id: [u8; 32], preimage: [u8; 32] },
+    // refund the expired hash time-locked transfer created by the lock transaction with id lock_id to its sender
+    HtlcRefund { lock_id: [u8; 32] },
+    // open a payment channel paying the recipient out of a deposit of amount
+    ChannelOpen,
+    // close the payment channel of the update, settling it with the update's paid amount
+    ChannelClose(ChannelUpdate),
+    // rebind the sending account to a new compressed public key, public keys are larger than the arrays serde supports
+    KeyRotation(#[serde(with = "BigArray")] [u8; COMPRESSED_PUBLIC_KEY_SIZE]),
+    // register the name to the sending account, burning amount as the registration fee
+    NameRegister([u8; MAX_NAME_SIZE]),
+    // extend the registration of a name owned by the sending account, burning amount as the registration fee
+    NameRenew([u8; MAX_NAME_SIZE]),
+    // transfer a name owned by the sending account to the recipient
+    NameTransfer([u8; MAX_NAME_SIZE]),
+}
+
+impl TxData {
+    pub fn from(raw: Vec<u8>) -> Result<Self, Box<ErrorKind>> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .deserialize(&raw)
+    }
+
+    pub fn serialize_tx_data(& self) -> Vec<u8> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize(self).unwrap()
+    }
+}
+
+// a bound of a transaction's validity window, in block height or in block timestamp
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TxLock {
+    // height of the block the transaction is added to
+    BlockHeight(u64),
+    // unix timestamp (in seconds) of the block the transaction is added to
+    Timestamp(u64),
+}
+
+impl TxLock {
+    pub fn from(raw: Vec<u8>) -> Result<Self, Box<ErrorKind>> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .deserialize(&raw)
+    }
+
+    pub fn serialize_tx_lock(& self) -> Vec<u8> {
+        bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize(self).unwrap()
+    }
 }
 
 impl Transaction {
@@ -36,7 +131,81 @@ impl Transaction {
             recipient,
             sender,
             signature,
-            nonce
+            nonce,
+            data: TxData::None,
+            multisig: None,
+            valid_after: None,
+            valid_until: None,
+            memo: vec![],
+            outputs: vec![],
+        }
+    }
+
+    pub fn with_data(mut self, data: TxData) -> Self {
+        // optional fields are set on the created transaction, transactions that don't use a field leave it at its default
+        self.data = data;
+        self
+    }
+
+    pub fn with_multisig(mut self, multisig: Multisig) -> Self {
+        self.multisig = Some(multisig);
+        self
+    }
+
+    pub fn with_validity_window(mut self, valid_after: Option<TxLock>, valid_until: Option<TxLock>) -> Self {
+        self.valid_after = valid_after;
+        self.valid_until = valid_until;
+        self
+    }
+
+    pub fn with_memo(mut self, memo: Vec<u8>) -> Self {
+        self.memo = memo;
+        self
+    }
+
+    pub fn with_outputs(mut self, outputs: Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>) -> Self {
+        self.outputs = outputs;
+        self
+    }
+
+    pub fn get_weight(&self) -> u64 {
+        // a batch transaction takes up block space and pays the base fee once for each of its outputs
+        self.outputs.len().max(1) as u64
+    }
+
+    pub fn get_tx_metadata(&self, sender: [u8; BLOCK_ADDRESS_SIZE], chain_id: [u8; 32]) -> TxMetadata {
+        // the part of the transaction that is signed by the sending account
+        TxMetadata::new(self.version, self.amount, self.fee, self.recipient, self.nonce).with_data(self.data).with_sender(sender).with_validity_window(self.valid_after, self.valid_until).with_memo(self.memo.clone()).with_outputs(self.outputs.clone()).with_chain_id(chain_id)
+    }
+
+    pub fn is_valid_at(&self, block_height: u64, timestamp: u64) -> bool {
+        // a transaction can only be added to a block within its validity window, both bounds are inclusive
+        let started = match self.valid_after {
+            Some(TxLock::BlockHeight(valid_after)) => block_height >= valid_after,
+            Some(TxLock::Timestamp(valid_after)) => timestamp >= valid_after,
+            None => true
+        };
+
+        started && !self.is_expired(block_height, timestamp)
+    }
+
+    pub fn is_expired(&self, block_height: u64, timestamp: u64) -> bool {
+        // once a transaction's valid until bound has passed it can never be added to a block
+        match self.valid_until {
+            Some(TxLock::BlockHeight(valid_until)) => block_height > valid_until,
+            Some(TxLock::Timestamp(valid_until)) => timestamp > valid_until,
+            None => false
+        }
+    }
+
+    pub fn get_sender_address(&self) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        // multisig accounts are identified by their keys and threshold, every other account by the sender public key
+        match &self.multisig {
+            Some(multisig) => Some(multisig.get_address()),
+            None => match PublicKey::from_sec1_bytes(&self.sender) {
+                Ok(sender_pub_key) => Some(Wallet::generate_address(&sender_pub_key, true)),
+                Err(_) => None
+            }
         }
     }
 
@@ -55,6 +224,16 @@ impl Transaction {
         Self::hash_serialized_tx(serialized_tx)
     }
 
+    pub fn serialize_hash_txid(& self) -> Vec<u8> {
+        // the transaction id is sha256(serialized transaction without its signatures), it only covers the signed content so it can't be changed by anyone relaying the transaction
+        let serialized_unsigned_tx = bincode::DefaultOptions::new()
+            .allow_trailing_bytes()
+            .with_fixint_encoding()
+            .with_big_endian()
+            .serialize(&UnsignedTransaction(self)).unwrap();
+        Self::hash_serialized_tx(serialized_unsigned_tx)
+    }
+
     pub fn serialize_tx(& self) -> Vec<u8> {
         bincode::DefaultOptions::new()
             .allow_trailing_bytes()
@@ -85,21 +264,206 @@ impl PartialOrd for Transaction {
 
 impl PartialEq for Transaction {
     fn eq(&self, other: &Self) -> bool {
-        (self.version, self.amount, self.fee, self.recipient, self.sender, self.signature, self.nonce) == 
-        (other.version, other.amount, other.fee, other.recipient, other.sender, other.signature, other.nonce)
+        (self.version, self.amount, self.fee, self.recipient, self.sender, self.signature, self.nonce, self.data, &self.multisig, self.valid_after, self.valid_until, &self.memo) == 
+        (other.version, other.amount, other.fee, other.recipient, other.sender, other.signature, other.nonce, other.data, &other.multisig, other.valid_after, other.valid_until, &other.memo) &&
+        self.outputs == other.outputs
     }
 }
 
 impl Eq for Transaction {}
 
-#[derive(Debug, Serialize, Deserialize)]
+// transactions are serialized as a tuple so that the data field is only present for versions that support it,
+// this keeps the serialization (and therefore the hashes) of version 1 transactions unchanged
+impl Serialize for Transaction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_fields(serializer, true)
+    }
+}
+
+// a transaction without its signature and multisig signatures, serialized for the transaction id
+struct UnsignedTransaction<'a>(&'a Transaction);
+
+impl Serialize for UnsignedTransaction<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_fields(serializer, false)
+    }
+}
+
+impl Transaction {
+    fn serialize_fields<S: Serializer>(&self, serializer: S, signed: bool) -> Result<S::Ok, S::Error> {
+        let has_data = self.version >= *DATA_TRANSACTION_VERSION;
+        let has_multisig = self.version >= *MULTISIG_TRANSACTION_VERSION;
+        let has_validity_window = self.version >= *VALIDITY_WINDOW_TRANSACTION_VERSION;
+        let has_memo = self.version >= *MEMO_TRANSACTION_VERSION;
+        let has_outputs = self.version >= *BATCH_TRANSACTION_VERSION;
+        let len = if has_outputs { 13 } else if has_memo { 12 } else if has_validity_window { 11 } else if has_multisig { 9 } else if has_data { 8 } else { 7 };
+        let mut tuple = serializer.serialize_tuple(if signed { len } else { len - 1 })?;
+        tuple.serialize_element(&self.version)?;
+        tuple.serialize_element(&self.amount)?;
+        tuple.serialize_element(&self.fee)?;
+        tuple.serialize_element(&Array(self.recipient))?;
+        tuple.serialize_element(&Array(self.sender))?;
+        if signed && self.version >= *SCHNORR_TRANSACTION_VERSION {
+            tuple.serialize_element(&self.signature)?;
+        } else if signed {
+            tuple.serialize_element(&Array(self.signature.to_bytes()))?;
+        }
+        tuple.serialize_element(&self.nonce)?;
+        if has_data {
+            tuple.serialize_element(&self.data)?;
+        }
+        if has_multisig {
+            // an unsigned multisig transaction keeps the account's threshold and keys but not their signatures
+            match (&self.multisig, signed) {
+                (Some(multisig), false) => tuple.serialize_element(&Some(Multisig::new(multisig.get_threshold(), multisig.get_public_keys(), vec![])))?,
+                _ => tuple.serialize_element(&self.multisig)?
+            }
+        }
+        if has_validity_window {
+            tuple.serialize_element(&self.valid_after)?;
+            tuple.serialize_element(&self.valid_until)?;
+        }
+        if has_memo {
+            tuple.serialize_element(&self.memo)?;
+        }
+        if has_outputs {
+            tuple.serialize_element(&serialize_outputs(&self.outputs))?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Transaction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TransactionVisitor;
+
+        impl<'de> Visitor<'de> for TransactionVisitor {
+            type Value = Transaction;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a serialized transaction")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Transaction, A::Error> {
+                let version: u8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let amount: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let fee: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let recipient: Array<u8, BLOCK_ADDRESS_SIZE> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                let sender: Array<u8, COMPRESSED_PUBLIC_KEY_SIZE> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(4, &self))?;
+                // only read the signature scheme if the version supports it
+                let signature = if version >= *SCHNORR_TRANSACTION_VERSION {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(5, &self))?
+                } else {
+                    let signature: ecdsa::Signature = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(5, &self))?;
+                    Signature::Ecdsa(signature)
+                };
+                let nonce: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(6, &self))?;
+                // only read the data field if the version supports it
+                let data = if version >= *DATA_TRANSACTION_VERSION {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(7, &self))?
+                } else {
+                    TxData::None
+                };
+                // only read the multisig field if the version supports it
+                let multisig = if version >= *MULTISIG_TRANSACTION_VERSION {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(8, &self))?
+                } else {
+                    None
+                };
+                // only read the validity window fields if the version supports them
+                let (valid_after, valid_until) = if version >= *VALIDITY_WINDOW_TRANSACTION_VERSION {
+                    (seq.next_element()?.ok_or_else(|| de::Error::invalid_length(9, &self))?, seq.next_element()?.ok_or_else(|| de::Error::invalid_length(10, &self))?)
+                } else {
+                    (None, None)
+                };
+                // only read the memo field if the version supports it
+                let memo = if version >= *MEMO_TRANSACTION_VERSION {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(11, &self))?
+                } else {
+                    vec![]
+                };
+                // only read the outputs field if the version supports it
+                let outputs = if version >= *BATCH_TRANSACTION_VERSION {
+                    let outputs: Vec<(Array<u8, BLOCK_ADDRESS_SIZE>, u64)> = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(12, &self))?;
+                    outputs.into_iter().map(|(recipient, amount)| (recipient.0, amount)).collect()
+                } else {
+                    vec![]
+                };
+
+                let transaction = Transaction::new(version, amount, fee, recipient.0, sender.0, signature, nonce).with_data(data).with_validity_window(valid_after, valid_until).with_memo(memo).with_outputs(outputs);
+                Ok(match multisig {
+                    Some(multisig) => transaction.with_multisig(multisig),
+                    None => transaction
+                })
+            }
+        }
+
+        // the length is the maximum number of fields, the visitor stops early for older versions
+        deserializer.deserialize_tuple(13, TransactionVisitor)
+    }
+}
+
+#[derive(Debug)]
 pub struct TxMetadata {
     pub version: u8,
     pub amount: u64,
     pub fee: u64,
-    #[serde(with = "serde_big_array::BigArray")]
     pub recipient: [u8; 39],
     pub nonce: u64,
+    pub data: TxData,
+    pub sender: [u8; BLOCK_ADDRESS_SIZE],
+    pub valid_after: Option<TxLock>,
+    pub valid_until: Option<TxLock>,
+    pub memo: Vec<u8>,
+    pub outputs: Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>,
+    pub chain_id: [u8; 32],
+}
+
+// like transactions, the data field is only part of the signed metadata for versions that support it
+// from MULTISIG_TRANSACTION_VERSION the sender's address is signed too, so a signature from a key of a multisig account can't be reused for the key's own account
+// from VALIDITY_WINDOW_TRANSACTION_VERSION the validity window is signed too, so it can't be widened by anyone relaying the transaction
+// from MEMO_TRANSACTION_VERSION the memo is signed too, and from BATCH_TRANSACTION_VERSION the outputs
+// from CHAIN_ID_TRANSACTION_VERSION the chain id is signed too, it isn't part of the transaction itself, every node fills in its own network's chain id
+impl Serialize for TxMetadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let has_data = self.version >= *DATA_TRANSACTION_VERSION;
+        let has_sender = self.version >= *MULTISIG_TRANSACTION_VERSION;
+        let has_validity_window = self.version >= *VALIDITY_WINDOW_TRANSACTION_VERSION;
+        let has_memo = self.version >= *MEMO_TRANSACTION_VERSION;
+        let has_outputs = self.version >= *BATCH_TRANSACTION_VERSION;
+        let has_chain_id = self.version >= *CHAIN_ID_TRANSACTION_VERSION;
+        let mut tuple = serializer.serialize_tuple(if has_chain_id { 12 } else if has_outputs { 11 } else if has_memo { 10 } else if has_validity_window { 9 } else if has_sender { 7 } else if has_data { 6 } else { 5 })?;
+        tuple.serialize_element(&self.version)?;
+        tuple.serialize_element(&self.amount)?;
+        tuple.serialize_element(&self.fee)?;
+        tuple.serialize_element(&Array(self.recipient))?;
+        tuple.serialize_element(&self.nonce)?;
+        if has_data {
+            tuple.serialize_element(&self.data)?;
+        }
+        if has_sender {
+            tuple.serialize_element(&Array(self.sender))?;
+        }
+        if has_validity_window {
+            tuple.serialize_element(&self.valid_after)?;
+            tuple.serialize_element(&self.valid_until)?;
+        }
+        if has_memo {
+            tuple.serialize_element(&self.memo)?;
+        }
+        if has_outputs {
+            tuple.serialize_element(&serialize_outputs(&self.outputs))?;
+        }
+        if has_chain_id {
+            tuple.serialize_element(&self.chain_id)?;
+        }
+        tuple.end()
+    }
+}
+
+// serde only supports arrays up to 32 elements, so the output addresses are wrapped like the other address fields
+fn serialize_outputs(outputs: &[([u8; BLOCK_ADDRESS_SIZE], u64)]) -> Vec<(Array<u8, BLOCK_ADDRESS_SIZE>, u64)> {
+    outputs.iter().map(|(recipient, amount)| (Array(*recipient), *amount)).collect()
 }
 
 impl TxMetadata {
@@ -110,9 +474,49 @@ impl TxMetadata {
             fee,
             recipient,
             nonce,
+            data: TxData::None,
+            // only signed from MULTISIG_TRANSACTION_VERSION, set with with_sender
+            sender: [0x00; BLOCK_ADDRESS_SIZE],
+            valid_after: None,
+            valid_until: None,
+            memo: vec![],
+            outputs: vec![],
+            // only signed from CHAIN_ID_TRANSACTION_VERSION, set with with_chain_id
+            chain_id: [0x00; 32],
         }
     }
 
+    pub fn with_data(mut self, data: TxData) -> Self {
+        self.data = data;
+        self
+    }
+
+    pub fn with_sender(mut self, sender: [u8; BLOCK_ADDRESS_SIZE]) -> Self {
+        self.sender = sender;
+        self
+    }
+
+    pub fn with_validity_window(mut self, valid_after: Option<TxLock>, valid_until: Option<TxLock>) -> Self {
+        self.valid_after = valid_after;
+        self.valid_until = valid_until;
+        self
+    }
+
+    pub fn with_memo(mut self, memo: Vec<u8>) -> Self {
+        self.memo = memo;
+        self
+    }
+
+    pub fn with_outputs(mut self, outputs: Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>) -> Self {
+        self.outputs = outputs;
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: [u8; 32]) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
     pub fn serialize_hash_tx_metadata(& self) -> Vec<u8> {
         // serialize transaction metadata
         let serialized_tx_metadata = self.serialize_tx_metadata();
@@ -0,0 +1,41 @@
+use crate::constants::{CHAIN_ID_TRANSACTION_VERSION, SCHNORR_TRANSACTION_VERSION};
+
+// The signature features new transactions are created with, chosen from the state of the chain_id and schnorr deployments
+#[derive(Debug, Clone, Copy)]
+pub struct TxFormat {
+    // chain id signed by transactions, all zeros until the chain_id deployment is active
+    chain_id: [u8; 32],
+    // whether the chain_id deployment is active
+    chain_id_active: bool,
+    // whether the schnorr deployment is active
+    schnorr_active: bool,
+}
+
+impl TxFormat {
+    pub fn new(chain_id: [u8; 32], chain_id_active: bool, schnorr_active: bool) -> Self {
+        Self {
+            chain_id,
+            chain_id_active,
+            schnorr_active,
+        }
+    }
+
+    pub fn get_chain_id(&self) -> [u8; 32] {
+        self.chain_id
+    }
+
+    pub fn get_version(&self, version: u8) -> u8 {
+        // the oldest version that supports the transaction's fields and the active signature features, each of these versions supports every field of the earlier versions
+        if self.schnorr_active {
+            version.max(*SCHNORR_TRANSACTION_VERSION)
+        } else if self.chain_id_active {
+            version.max(*CHAIN_ID_TRANSACTION_VERSION)
+        } else {
+            version
+        }
+    }
+
+    pub fn is_schnorr_active(&self) -> bool {
+        self.schnorr_active
+    }
+}
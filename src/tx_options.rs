@@ -0,0 +1,45 @@
+use crate::transaction::TxLock;
+
+// Optional fields of a payment chosen by its sender, fields that aren't set are left out of the transaction
+#[derive(Debug, Clone, Default)]
+pub struct TxOptions {
+    // earliest block the transaction can be added to
+    valid_after: Option<TxLock>,
+    // latest block the transaction can be added to, after which it expires
+    valid_until: Option<TxLock>,
+    // payment reference of at most MAX_MEMO_SIZE bytes, empty for no memo
+    memo: Vec<u8>,
+}
+
+impl TxOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_validity_window(mut self, valid_after: Option<TxLock>, valid_until: Option<TxLock>) -> Self {
+        self.valid_after = valid_after;
+        self.valid_until = valid_until;
+        self
+    }
+
+    pub fn with_memo(mut self, memo: Vec<u8>) -> Self {
+        self.memo = memo;
+        self
+    }
+
+    pub fn get_valid_after(&self) -> Option<TxLock> {
+        self.valid_after
+    }
+
+    pub fn get_valid_until(&self) -> Option<TxLock> {
+        self.valid_until
+    }
+
+    pub fn has_validity_window(&self) -> bool {
+        self.valid_after.is_some() || self.valid_until.is_some()
+    }
+
+    pub fn get_memo(&self) -> Vec<u8> {
+        self.memo.clone()
+    }
+}
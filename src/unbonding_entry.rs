@@ -0,0 +1,34 @@
+use crate::constants::BLOCK_ADDRESS_SIZE;
+
+// Revoked stake waiting to be released back to an account's balance
+#[derive(Debug, Clone)]
+pub struct UnbondingEntry {
+    // address of the account the stake will be released to
+    address: [u8; BLOCK_ADDRESS_SIZE],
+    // amount of stake that is unbonding
+    amount: u64,
+    // block height at which the stake is released to the account's balance
+    release_height: u64,
+}
+
+impl UnbondingEntry {
+    pub fn new(address: [u8; BLOCK_ADDRESS_SIZE], amount: u64, release_height: u64) -> Self {
+        Self {
+            address,
+            amount,
+            release_height,
+        }
+    }
+
+    pub fn get_address(&self) -> [u8; BLOCK_ADDRESS_SIZE] {
+        self.address
+    }
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount
+    }
+
+    pub fn get_release_height(&self) -> u64 {
+        self.release_height
+    }
+}
\ No newline at end of file
@@ -34,6 +34,20 @@ pub fn read_file_from_beginning(mut file: File) -> Result<String, io::Error> {
     Ok(string)
 }
 
+// encode bytes as a lower case hex string
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// decode a hex string into bytes, upper or lower case
+pub fn decode_hex(string: &str) -> Option<Vec<u8>> {
+    if !string.len().is_multiple_of(2) {
+        return None
+    }
+
+    (0..string.len()).step_by(2).map(|index| u8::from_str_radix(string.get(index..index + 2)?, 16).ok()).collect()
+}
+
 // read user input
 pub fn read_string() -> String {
     // helper function to read user input from the command line, removes leading and trailing spaces
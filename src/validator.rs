@@ -1,10 +1,9 @@
-use k256::ecdsa::Signature;
-
 use crate::block::Block;
 use crate::config::ValidatorConfig;
+use crate::signature::Signature;
 use crate::transaction::Transaction;
 
-use crate::constants::BLOCK_VERSION;
+use crate::constants::VRF_PROOF_SIZE;
 
 pub struct Validator {
     // config for the validator
@@ -22,9 +21,9 @@ impl Validator {
         self.config.clone()
     }
 
-    pub fn create_block(&mut self, transactions: &mut Vec<Transaction>, prev_hash: [u8; 32], timestamp: u64, block_sig: Signature) -> Block {
+    pub fn create_block(&mut self, version: u32, transactions: &mut Vec<Transaction>, prev_hash: [u8; 32], timestamp: u64, vrf_proof: [u8; VRF_PROOF_SIZE], block_sig: Signature) -> Block {
         // create the new block
-        Block::new(*BLOCK_VERSION, prev_hash, timestamp, &transactions, block_sig)
+        Block::new(version, prev_hash, timestamp, vrf_proof, &transactions, block_sig)
     }
 }
 
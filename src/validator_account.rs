@@ -1,19 +1,80 @@
-use crate::constants::COMPRESSED_PUBLIC_KEY_SIZE;
+use crate::delegation::Delegation;
+
+use crate::constants::{BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE, DEFAULT_VALIDATOR_COMMISSION};
 
 #[derive(Debug, Clone)]
 pub struct ValidatorAccount {
     // public key of the validator
     public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE],
+    // block height from which the validator can be chosen as a block proposer, new validators are on probation until then
+    activation_height: u64,
+    // commission the validator keeps from the rewards owed to its delegators in basis points
+    commission: u16,
+    // stake delegated to the validator by other accounts
+    delegations: Vec<Delegation>,
 }
 
 impl ValidatorAccount {
-    pub fn new(public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE]) -> Self {
+    pub fn new(public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE], activation_height: u64) -> Self {
         Self {
             public_key,
+            activation_height,
+            commission: *DEFAULT_VALIDATOR_COMMISSION,
+            delegations: vec![],
         }
     }
 
     pub fn get_public_key(&self) -> [u8; COMPRESSED_PUBLIC_KEY_SIZE] {
         self.public_key
     }
+
+    pub fn set_public_key(&mut self, public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE]) {
+        self.public_key = public_key
+    }
+
+    pub fn is_active(&self, block_height: u64) -> bool {
+        self.activation_height <= block_height
+    }
+
+    pub fn set_commission(&mut self, commission: u16) {
+        self.commission = commission
+    }
+
+    pub fn get_commission(&self) -> u16 {
+        self.commission
+    }
+
+    pub fn get_delegations(&self) -> Vec<Delegation> {
+        self.delegations.clone()
+    }
+
+    pub fn get_delegation(&self, delegator: &[u8; BLOCK_ADDRESS_SIZE]) -> u64 {
+        // get the amount the delegator has delegated to this validator, 0 if they haven't delegated
+        match self.delegations.iter().find(|delegation| delegation.get_delegator() == *delegator) {
+            Some(delegation) => delegation.get_amount(),
+            None => 0
+        }
+    }
+
+    pub fn get_delegated_stake(&self) -> u64 {
+        // total stake delegated to this validator
+        self.delegations.iter().map(|delegation| delegation.get_amount()).sum()
+    }
+
+    pub fn add_delegation(&mut self, delegator: [u8; BLOCK_ADDRESS_SIZE], amount: u64) {
+        // there is at most one delegation per delegator, add to it if it already exists
+        match self.delegations.iter_mut().find(|delegation| delegation.get_delegator() == delegator) {
+            Some(delegation) => delegation.increase_amount(amount),
+            None => self.delegations.push(Delegation::new(delegator, amount))
+        }
+    }
+
+    pub fn remove_delegation(&mut self, delegator: [u8; BLOCK_ADDRESS_SIZE], amount: u64) {
+        // decrease the delegation, removing it entirely once nothing is left delegated
+        if let Some(delegation) = self.delegations.iter_mut().find(|delegation| delegation.get_delegator() == delegator) {
+            delegation.decrease_amount(amount);
+        }
+
+        self.delegations.retain(|delegation| delegation.get_amount() > 0);
+    }
 }
\ No newline at end of file
@@ -1,37 +1,107 @@
-use k256::ecdsa::{VerifyingKey, signature::Verifier, Signature};
-use k256::PublicKey;
+use std::time::SystemTime;
 
+use k256::ecdsa::{self, RecoveryId, VerifyingKey, signature::Verifier};
+use k256::elliptic_curve::ops::{LinearCombinationExt, Reduce};
+use k256::elliptic_curve::point::DecompactPoint;
+use k256::{schnorr, AffinePoint, FieldBytes, NonZeroScalar, ProjectivePoint, Scalar, U256};
+use rand_core::OsRng;
+use sha2::{Digest, Sha256};
+
+use crate::attestation::Attestation;
 use crate::block::{Block, BlockHeader};
+use crate::channel_update::ChannelUpdate;
+use crate::multisig::Multisig;
+use crate::name_record::NameRecord;
+use crate::signature::Signature;
 use crate::blockchain::Blockchain;
-use crate::transaction::{Transaction, TxMetadata};
-use crate::wallet::Wallet;
+use crate::transaction::{Transaction, TxData, TxMetadata};
+use crate::vrf;
 
-use crate::constants::{BOOTSTRAPPING_PHASE_BLOCK_HEIGHT, COINBASE_SENDER, COMPRESSED_PUBLIC_KEY_SIZE, HALVING_INTERVAL, LOOSE_CHANGE_RECIPIENT, LOWEST_DENOMINATION_PER_COIN, MAX_TRANSACTIONS_PER_BLOCK, MINIMUM_STAKING_AMOUNT, VALIDATOR_ENABLE_RECIPIENT, VALIDATOR_REVOKE_RECIPIENT};
+use crate::constants::{BATCH_RECIPIENT, BATCH_TRANSACTION_VERSION, BLOCK_ADDRESS_SIZE, BOOTSTRAPPING_PHASE_BLOCK_HEIGHT, BURN_RECIPIENT, CHANNEL_RECIPIENT, CHECKPOINT_INTERVAL, COINBASE_SENDER, COMPRESSED_PUBLIC_KEY_SIZE, HALVING_INTERVAL, HTLC_RECIPIENT, KEY_ROTATION_RECIPIENT, LOOSE_CHANGE_RECIPIENT, LOWEST_DENOMINATION_PER_COIN, MAX_TRANSACTIONS_PER_BLOCK, MAX_BATCH_OUTPUTS, MAX_MEMO_SIZE, MAX_VALIDATOR_COMMISSION, MAX_MULTISIG_PUBLIC_KEYS, MAX_VALIDATOR_ENABLES_PER_BLOCK, MEMO_TRANSACTION_VERSION, MINIMUM_STAKING_AMOUNT, MULTISIG_SENDER, MULTISIG_TRANSACTION_VERSION, NAME_RECIPIENT, NAME_REGISTRATION_FEE, SCHNORR_BLOCK_VERSION, SCHNORR_TRANSACTION_VERSION, TRANSACTION_VERSION, VALIDATOR_COMMISSION_RECIPIENT, VALIDATOR_ENABLE_RECIPIENT, VALIDATOR_REVOKE_RECIPIENT, VALIDATOR_STAKE_INCREASE_RECIPIENT, VALIDATOR_STAKE_WITHDRAW_RECIPIENT, VRF_BLOCK_VERSION};
 
 pub fn verify_transaction(transaction: &Transaction, block: Option<&Block>, blockchain: &Blockchain) -> bool {
-    if !is_coinbase(transaction, block, blockchain.get_block_height()) {
-        // compute the TxMetadata struct from the given transaction
-        let hashed_serialized_tx_metadata = TxMetadata::serialize_hash_tx_metadata(&TxMetadata::new(transaction.version, transaction.amount, transaction.fee, transaction.recipient, transaction.nonce));
-        let verifying_key = match VerifyingKey::from_sec1_bytes(&transaction.sender) {
-            Ok(verifying_key) => verifying_key,
+    let mut schnorr_batch = vec![];
+    verify_transaction_batched(transaction, block, blockchain, &mut schnorr_batch) && verify_schnorr_batch(&schnorr_batch)
+}
+
+fn verify_transaction_batched(transaction: &Transaction, block: Option<&Block>, blockchain: &Blockchain, schnorr_batch: &mut Vec<([u8; COMPRESSED_PUBLIC_KEY_SIZE], Vec<u8>, schnorr::Signature)>) -> bool {
+    // the transaction's Schnorr signature is added to the Schnorr batch instead of being verified, the caller must verify the batch
+    // confirm the transaction version is one this node understands
+    if transaction.version < *TRANSACTION_VERSION || transaction.version > blockchain.get_max_transaction_version() {
+        return false
+    }
+
+    // confirm only transactions of SCHNORR_TRANSACTION_VERSION or later are signed with Schnorr, once the schnorr deployment is active
+    if !transaction.signature.is_ecdsa() && (transaction.version < *SCHNORR_TRANSACTION_VERSION || !blockchain.is_deployment_active("schnorr")) {
+        return false
+    }
+
+    // confirm the memo isn't larger than allowed, only transactions of MEMO_TRANSACTION_VERSION or later can carry one, once the memo deployment is active
+    if transaction.memo.len() > MAX_MEMO_SIZE || (!transaction.memo.is_empty() && (transaction.version < *MEMO_TRANSACTION_VERSION || !blockchain.is_deployment_active("memo"))) {
+        return false
+    }
+
+    // confirm only transactions sent once the validity window deployment is active have a validity window
+    if (transaction.valid_after.is_some() || transaction.valid_until.is_some()) && !blockchain.is_deployment_active("validity_window") {
+        return false
+    }
+
+    // confirm only valid batch transactions carry outputs and every transaction to the BATCH_RECIPIENT is a valid batch transaction, once the batch deployment is active
+    if (transaction.recipient == *BATCH_RECIPIENT || !transaction.outputs.is_empty()) && (!blockchain.is_deployment_active("batch") || !is_batch(transaction)) {
+        return false
+    }
+
+    if !is_coinbase(transaction, block, blockchain) {
+        // confirm the transaction pays at least the base fee for each unit of its weight, the base fee is burned and only the rest of the fee is paid to the block validator
+        if transaction.fee < blockchain.get_base_fee() * transaction.get_weight() {
+            return false
+        }
+
+        // confirm the transaction is within its validity window, a transaction being added to the mempool must be valid for the next block at the current time
+        let timestamp = match block {
+            Some(block) => block.get_timesamp(),
+            None => match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+                Ok(timestamp) => timestamp.as_secs(),
+                Err(_) => return false
+            }
+        };
+        if !transaction.is_valid_at(blockchain.get_block_height() + 1, timestamp) {
+            return false
+        }
+
+        // get the address of the sending account, a multisig account's address comes from its keys and threshold
+        let account_address = match blockchain.get_sender_address(transaction) {
+            Some(account_address) => account_address,
             // if an invalid public key is received then the transaction is invalid
-            Err(_) => return false
+            None => return false
         };
 
-        // verify the signature and message with the received public key
-        if !verify_sig(&verifying_key, &hashed_serialized_tx_metadata, &transaction.signature) {
+        // once the key of an account has been rotated, the key its address was generated from can no longer sign for it
+        if transaction.multisig.is_none() && blockchain.get_account(&account_address).and_then(|account| account.get_public_key()).is_some_and(|public_key| public_key != transaction.sender) {
             return false
         }
 
-        // get the account public key
-        let account_pub_key = match PublicKey::from_sec1_bytes(&transaction.sender) {
-            Ok(account_pub_key) => account_pub_key,
-            // if an invalid public key is received then the transaction is invalid
-            Err(_) => return false
+        // compute the TxMetadata struct from the given transaction
+        let hashed_serialized_tx_metadata = TxMetadata::serialize_hash_tx_metadata(&transaction.get_tx_metadata(account_address, blockchain.get_signed_chain_id()));
+
+        // verify the signatures of the sending account
+        let signed = match &transaction.multisig {
+            // multisig accounts can only send transactions once the multisig deployment is active
+            Some(multisig) => blockchain.is_deployment_active("multisig") && verify_multisig(transaction, multisig, &hashed_serialized_tx_metadata),
+            None => match &transaction.signature {
+                // Schnorr signatures of a block's transactions are verified together once every transaction in the block has been checked
+                Signature::Schnorr(signature) => {
+                    schnorr_batch.push((transaction.sender, hashed_serialized_tx_metadata.clone(), *signature));
+                    true
+                },
+                // verify the signature and message with the received public key
+                Signature::Ecdsa(_) => verify_signature(&transaction.sender, &hashed_serialized_tx_metadata, &transaction.signature)
+            }
         };
 
-        // get the address for the account public key
-        let account_address = Wallet::generate_address(&account_pub_key, true);
+        if !signed {
+            return false
+        }
 
         // obtain the account nonce in the blockchains view
         let tx_account_nonce = match blockchain.get_account(&account_address) {
@@ -56,7 +126,7 @@ pub fn verify_transaction(transaction: &Transaction, block: Option<&Block>, bloc
         }
 
         // check for proper balances
-        if is_validator_enable(transaction, blockchain) {
+        if is_validator_enable(transaction, block, blockchain) {
             // obtain the sender balance
             let account_balance = match blockchain.get_account(&account_address) {
                 Some(tx_account) => {
@@ -80,13 +150,210 @@ pub fn verify_transaction(transaction: &Transaction, block: Option<&Block>, bloc
                 None => 0
             };
 
+            // confirm the sender's balance is at least the transaction fee
+            if account_balance < transaction.fee {
+                return false
+            }
+        } else if is_validator_stake_increase(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // only validators can increase their stake so the account must exist, technically should never be able to get here
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the amount being added to their stake and the transaction fee
+            if account_balance < (transaction.fee + transaction.amount) {
+                return false
+            }
+        } else if is_validator_stake_withdraw(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // only validators can withdraw stake so the account must exist, technically should never be able to get here
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the transaction fee, the withdrawn amount comes from their stake
+            if account_balance < transaction.fee {
+                return false
+            }
+        } else if is_delegate(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // if the account is not within the blockchain then it definitely doesn't have sufficient funds
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the amount being delegated and the transaction fee
+            if account_balance < (transaction.fee + transaction.amount) {
+                return false
+            }
+        } else if is_undelegate(transaction, blockchain) || is_validator_commission(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // only delegators and validators can send these transactions so the account must exist, technically should never be able to get here
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the transaction fee, an undelegated amount comes from the delegation
+            if account_balance < transaction.fee {
+                return false
+            }
+        } else if is_batch(transaction) {
+            // confirm no output pays an address no one can spend from
+            if transaction.outputs.iter().any(|(recipient, _)| blockchain.is_rotated_key_address(recipient)) {
+                return false
+            }
+
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // if the account is not within the blockchain then it definitely doesn't have sufficient funds
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the total of the outputs and the transaction fee
+            if account_balance < (transaction.fee + transaction.amount) {
+                return false
+            }
+        } else if is_htlc_lock(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // if the account is not within the blockchain then it definitely doesn't have sufficient funds
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the amount being locked and the transaction fee
+            if account_balance < (transaction.fee + transaction.amount) {
+                return false
+            }
+        } else if is_htlc_claim(transaction, blockchain) || is_htlc_refund(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // the claimer of a transfer may not have an account yet, it can pay the fee out of the unlocked funds
+                None => 0
+            };
+
+            // obtain the amount being unlocked
+            let unlocked_amount = match transaction.data {
+                TxData::HtlcClaim { lock_id, .. } | TxData::HtlcRefund { lock_id } => match blockchain.get_htlc(&lock_id) {
+                    Some(htlc) => htlc.get_amount(),
+                    None => return false
+                },
+                _ => return false
+            };
+
+            // confirm the sender's balance and the unlocked amount together are at least the transaction fee
+            if account_balance + unlocked_amount < transaction.fee {
+                return false
+            }
+        } else if is_channel_open(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // if the account is not within the blockchain then it definitely doesn't have sufficient funds
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the deposit and the transaction fee
+            if account_balance < (transaction.fee + transaction.amount) {
+                return false
+            }
+        } else if is_channel_close(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // the payee of a channel may not have an account yet, it can pay the fee out of the settled funds
+                None => 0
+            };
+
+            // obtain the amount the payee receives right away, a channel closed by its payer is only settled after the dispute period
+            let settled_amount = match transaction.data {
+                TxData::ChannelClose(channel_update) => match blockchain.get_payment_channel(&channel_update.get_channel_id()) {
+                    Some(channel) if channel.get_payee() == account_address => channel_update.get_paid(),
+                    Some(_) => 0,
+                    None => return false
+                },
+                _ => return false
+            };
+
+            // confirm the sender's balance and the settled amount together are at least the transaction fee
+            if account_balance + settled_amount < transaction.fee {
+                return false
+            }
+        } else if is_key_rotation(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // if the account is not within the blockchain then it definitely doesn't have sufficient funds
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the transaction fee
+            if account_balance < transaction.fee {
+                return false
+            }
+        } else if is_name_registration(transaction, blockchain) || is_name_renewal(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // if the account is not within the blockchain then it definitely doesn't have sufficient funds
+                None => return false
+            };
+
+            // confirm the sender's balance is at least the registration fee and the transaction fee
+            if account_balance < (transaction.fee + transaction.amount) {
+                return false
+            }
+        } else if is_name_transfer(transaction, blockchain) {
+            // obtain the sender balance
+            let account_balance = match blockchain.get_account(&account_address) {
+                Some(tx_account) => {
+                    tx_account.get_balance()
+                },
+                // if the account is not within the blockchain then it definitely doesn't have sufficient funds
+                None => return false
+            };
+
             // confirm the sender's balance is at least the transaction fee
             if account_balance < transaction.fee {
                 return false
             }
         } else {
-            // confirm the sender isn't trying to send to any of the "special" addresses 
-            if transaction.recipient == *VALIDATOR_ENABLE_RECIPIENT || transaction.recipient == *VALIDATOR_REVOKE_RECIPIENT || transaction.recipient == *LOOSE_CHANGE_RECIPIENT {
+            // confirm the transaction doesn't carry data, any transaction with data must be one of the types above
+            if transaction.data != TxData::None {
+                return false
+            }
+
+            // confirm the sender isn't trying to send to any of the "special" addresses or to an address no one can spend from
+            if is_special_recipient(&transaction.recipient) || blockchain.is_rotated_key_address(&transaction.recipient) {
                 return false
             }
             
@@ -114,13 +381,76 @@ pub fn verify_transaction(transaction: &Transaction, block: Option<&Block>, bloc
     true
 }
 
-fn verify_sig(verifying_key: &VerifyingKey, message: &Vec<u8>, signature: &Signature) -> bool {
+fn verify_signature(public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE], message: &Vec<u8>, signature: &Signature) -> bool {
+    match signature {
+        Signature::Ecdsa(signature) => match VerifyingKey::from_sec1_bytes(public_key) {
+            Ok(verifying_key) => verify_sig(&verifying_key, message, signature),
+            // if an invalid public key is received then the signature is invalid
+            Err(_) => false
+        },
+        // Schnorr signatures are verified with the x coordinate of the compressed public key, the message is already a sha256 hash so it is signed as is
+        Signature::Schnorr(signature) => match schnorr::VerifyingKey::from_bytes(&public_key[1..]) {
+            Ok(verifying_key) => verifying_key.verify_raw(message, signature).is_ok(),
+            Err(_) => false
+        }
+    }
+}
+
+fn verify_sig(verifying_key: &VerifyingKey, message: &Vec<u8>, signature: &ecdsa::Signature) -> bool {
+    // only canonical low S signatures are valid, otherwise anyone could negate S to create a second valid signature for the same message
+    if !is_low_s(signature) {
+        return false
+    }
+
     match verifying_key.verify(message, signature) {
         Ok(_) => return true,
         Err(_) => return false
     }
 }
 
+fn is_low_s(signature: &ecdsa::Signature) -> bool {
+    // normalize_s only returns a signature if S was high
+    signature.normalize_s().is_none()
+}
+
+fn verify_multisig(transaction: &Transaction, multisig: &Multisig, message: &Vec<u8>) -> bool {
+    // only transactions of MULTISIG_TRANSACTION_VERSION or later can be sent from multisig accounts, with the MULTISIG_SENDER as the sender
+    if transaction.version < *MULTISIG_TRANSACTION_VERSION || transaction.sender != *MULTISIG_SENDER {
+        return false
+    }
+
+    let public_keys = multisig.get_public_keys();
+    let signatures = multisig.get_signatures();
+
+    // confirm the account has between 1 and MAX_MULTISIG_PUBLIC_KEYS keys, sorted without duplicates so every key set has exactly one address
+    if public_keys.is_empty() || public_keys.len() > MAX_MULTISIG_PUBLIC_KEYS || !public_keys.windows(2).all(|keys| keys[0] < keys[1]) {
+        return false
+    }
+
+    // confirm the threshold can be met by the account's keys
+    if multisig.get_threshold() == 0 || multisig.get_threshold() as usize > public_keys.len() {
+        return false
+    }
+
+    // confirm there are exactly threshold signatures from different keys sorted by key index, so a transaction can't be changed by adding extra signatures
+    if signatures.len() != multisig.get_threshold() as usize || !signatures.windows(2).all(|signatures| signatures[0].0 < signatures[1].0) {
+        return false
+    }
+
+    // confirm the transaction signature is the first multisig signature, multisig accounts are always signed with ECDSA
+    if transaction.signature != Signature::Ecdsa(signatures[0].1) {
+        return false
+    }
+
+    // verify every signature with the key at its index
+    signatures.iter().all(|(index, signature)| {
+        match public_keys.get(*index as usize).map(|public_key| VerifyingKey::from_sec1_bytes(public_key)) {
+            Some(Ok(verifying_key)) => verify_sig(&verifying_key, message, signature),
+            _ => false
+        }
+    })
+}
+
 pub fn verify_block(block: Block, blockchain: &Blockchain) -> bool {
     // temporary blockchain to maintain state within the block and its transactions
     let mut new_blockchain = blockchain.clone();
@@ -130,8 +460,26 @@ pub fn verify_block(block: Block, blockchain: &Blockchain) -> bool {
         return false
     }
 
-    // confirm the transaction list doesn't have more than the MAX_TRANSACTIONS_PER_BLOCK
-    if block.get_transactions().len() > *MAX_TRANSACTIONS_PER_BLOCK {
+    // confirm the block is the trusted checkpoint at its height, if there is one
+    if let Some(checkpoint_hash) = blockchain.get_trusted_checkpoint(blockchain.get_block_height() + 1) {
+        if block.serialize_hash_block_header() != checkpoint_hash.to_vec() {
+            return false
+        }
+    }
+
+    // confirm the block format version is one this node understands, every block after the genesis block must carry a vrf proof
+    // the rest of the version bits are deployment signals, unknown signals are allowed
+    if block.get_format_version() < *VRF_BLOCK_VERSION || block.get_format_version() > blockchain.get_max_block_version() {
+        return false
+    }
+
+    // confirm only blocks of SCHNORR_BLOCK_VERSION or later are signed with Schnorr, once the schnorr deployment is active
+    if !block.get_signature().is_ecdsa() && (block.get_format_version() < *SCHNORR_BLOCK_VERSION || !blockchain.is_deployment_active("schnorr")) {
+        return false
+    }
+
+    // confirm the transaction list doesn't have more than the MAX_TRANSACTIONS_PER_BLOCK, a batch transaction counts once for each of its outputs
+    if block.get_weight() > *MAX_TRANSACTIONS_PER_BLOCK as u64 {
         return false
     }
 
@@ -147,75 +495,39 @@ pub fn verify_block(block: Block, blockchain: &Blockchain) -> bool {
     // Validator tries to propogate a block before the actual validator sends theirs. Need to add time increment (2min?) in between picking new validator (must check in real time when blocks are proposed)
     // Need to also remove validators that consistently never propose a block, or malicious actors can (during the bootstrapping phase) create an unlimted number of validators that don't propose blocks and halts the network
 
-    // confirm the validator that proposed the block is the one that should have proposed it
-    let mut proposer_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE];
-    // the index within the validator vector on the blockchain 
-    let mut proposer_pub_key_index: usize;
-
-    // the pub key of the previously attempted validator 
-    let mut previous_validator_pub_key: Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> = None;
-
-    // initial validator list that can be chosen from to get the block proposer 
-    let mut validator_list = blockchain.get_validators().clone();
-
-    // if there are no validators on the blockchain, then game over no new blocks can be added
-    if validator_list.len() == 0 {
-        return false
-    }
-
-    for i in 0..blockchain.get_validators().len() {
-        // obtain the public key of the validator that was chosen to propose this block and use use the previously chosen validatyor pub key (if there was one) as a "seed" for choosing the next validator 
-        (proposer_pub_key, proposer_pub_key_index) = match blockchain.calculate_proposer(validator_list.clone(), previous_validator_pub_key) {
-            Some((proposer_pub_key, proposer_pub_key_index)) => (proposer_pub_key, proposer_pub_key_index),
-            // should never get here
-            None => continue
-        };
-
-        let verifying_key = match VerifyingKey::from_sec1_bytes(&proposer_pub_key) {
-            Ok(verifying_key) => verifying_key,
-            // if an invalid public key is received then try the next validator
-            Err(_) => continue
-        };
-
-        // get the contents of what the block signature should contain
-        let hashed_serialized_block_header = block.serialize_hash_block_header();
-
-        // verify the signature and message with the received public key
-        if verify_sig(&verifying_key, &hashed_serialized_block_header, &block.get_signature()) {
-            // if the signature is valid then make sure the timestamp is correct
-
-            // get the timestamp of the previous block
-            let previous_block_timestamp = blockchain.get_last_block().get_timesamp();
-
-            // get the proposed blocks timestamp
-            let current_block_timestamp = block.get_timesamp();
-
-            // timestamp of incoming block should not be less than 5 min after the previous block + 2 minutes for every new validator that would have been chosen
-            // ToDo: need to do real time checking on blocks received in real time to make sure timestamp matches with real time
-            if current_block_timestamp - previous_block_timestamp < 300 + TryInto::<u64>::try_into(i).unwrap() * 120 {
-                return false
-            }
-
-            // if signature is valid and timestamp match then the proposer is valid
-            break;
+    let (proposer_pub_key, earliest_timestamp) = if is_below_trusted_checkpoint(blockchain) {
+        // blocks at or below the last trusted checkpoint are linked to it by their previous hashes, so the proposer schedule isn't searched
+        // the proposer's place in the schedule isn't known, so the block can't be earlier than the first validator in the schedule could have proposed it
+        match recover_block_proposer(&block, blockchain) {
+            Some(proposer_pub_key) => (proposer_pub_key, blockchain.get_last_block().get_timesamp() + 300),
+            None => return false
         }
-
-        // if all all validators have been exhausted and the signature doesnt match then this is an invalid block
-        if i == blockchain.get_validators().len() - 1 {
-            return false
+    } else {
+        // confirm the validator that proposed the block is the one that should have proposed it
+        match get_block_proposer(&block, blockchain) {
+            Some(proposer) => proposer,
+            None => return false
         }
+    };
 
-        // set previous_validator_pub_key here
-        previous_validator_pub_key = Some(proposer_pub_key);
+    // confirm the vrf proof was created by the proposer over the current randomness beacon
+    if vrf::verify(&proposer_pub_key, &blockchain.get_randomness_beacon(), &block.get_vrf_proof()).is_none() {
+        return false
+    }
 
-        // remove the previous validator from the possible validators list
-        validator_list.remove(proposer_pub_key_index);
+    // timestamp of incoming block should not be less than 5 min after the previous block + 2 minutes for every new validator that would have been chosen
+    // ToDo: need to do real time checking on blocks received in real time to make sure timestamp matches with real time
+    if block.get_timesamp() < earliest_timestamp {
+        return false
     }
 
+    // Schnorr signed transactions of the block, their signatures are verified in a single batch
+    let mut schnorr_batch = vec![];
+
     for transaction in block.get_transactions() {
         // verify each transaction and update the local copy of the blockchain
-        if verify_transaction(transaction, Some(&block), &new_blockchain) {
-            if !new_blockchain.update_chain_transaction(transaction, &block) {
+        if verify_transaction_batched(transaction, Some(&block), &new_blockchain, &mut schnorr_batch) {
+            if !new_blockchain.update_chain_transaction(transaction, &block, Some(proposer_pub_key)) {
                 return false
             }
         } else {
@@ -223,13 +535,156 @@ pub fn verify_block(block: Block, blockchain: &Blockchain) -> bool {
         }
     }
 
-    true
+    verify_schnorr_batch(&schnorr_batch)
 }
 
-pub fn is_coinbase(transaction: &Transaction, block: Option<&Block>, block_height: u64) -> bool {
-    // the signature of a coinbase transaction only needs to be a valid signature, its contents are never checked
+fn verify_schnorr_batch(batch: &[([u8; COMPRESSED_PUBLIC_KEY_SIZE], Vec<u8>, schnorr::Signature)]) -> bool {
+    // BIP340 batch verification, every signature (r, s) with challenge e is valid if s * G = R + e * P, where R is the point with x coordinate r and an even y coordinate
+    // the equations are multiplied by random coefficients and added together, so the whole batch is checked with one multi-scalar multiplication
+    // the first coefficient is 1, the rest are random so invalid signatures can't be chosen to cancel each other out
+    let challenge_tag = Sha256::digest(b"BIP0340/challenge");
+    let mut s_sum = Scalar::ZERO;
+    let mut points_and_scalars = vec![];
 
-    // if there is no block passed in, then it cannot be a valid coinbase transaction
+    for (index, (public_key, message, signature)) in batch.iter().enumerate() {
+        // Schnorr signatures are verified with the x coordinate of the compressed public key
+        let verifying_key = match schnorr::VerifyingKey::from_bytes(&public_key[1..]) {
+            Ok(verifying_key) => verifying_key,
+            // if an invalid public key is received then the signature is invalid
+            Err(_) => return false
+        };
+
+        let signature_bytes = signature.to_bytes();
+        let r_bytes: [u8; 32] = signature_bytes[..32].try_into().unwrap();
+        let s_bytes: [u8; 32] = signature_bytes[32..].try_into().unwrap();
+
+        let nonce_point = match Option::<AffinePoint>::from(AffinePoint::decompact(&FieldBytes::from(r_bytes))) {
+            Some(nonce_point) => nonce_point,
+            None => return false
+        };
+
+        // s is already known to be less than the curve order since the signature was parsed
+        let s = <Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::from(s_bytes));
+        let e = <Scalar as Reduce<U256>>::reduce_bytes(&Sha256::new()
+            .chain_update(challenge_tag)
+            .chain_update(challenge_tag)
+            .chain_update(r_bytes)
+            .chain_update(&public_key[1..])
+            .chain_update(message)
+            .finalize());
+
+        let coefficient = if index == 0 { Scalar::ONE } else { *NonZeroScalar::random(&mut OsRng) };
+
+        s_sum += coefficient * s;
+        points_and_scalars.push((ProjectivePoint::from(nonce_point), -coefficient));
+        points_and_scalars.push((verifying_key.as_affine().into(), -(coefficient * e)));
+    }
+
+    // sum(a * s) * G - sum(a * R) - sum(a * e * P) is the identity if every signature is valid
+    points_and_scalars.push((ProjectivePoint::GENERATOR, s_sum));
+    ProjectivePoint::lincomb_ext(points_and_scalars.as_slice()) == ProjectivePoint::IDENTITY
+}
+
+pub fn verify_attestation(attestation: &Attestation, blockchain: &Blockchain) -> bool {
+    // confirm the attested block is a checkpoint that isn't already final
+    if !attestation.get_block_height().is_multiple_of(*CHECKPOINT_INTERVAL) || attestation.get_block_height() <= blockchain.get_finalized_height() {
+        return false
+    }
+
+    // confirm the attested block is the block at that height on this blockchain
+    match blockchain.get_block(attestation.get_block_height()) {
+        Some(block) => {
+            if block.serialize_hash_block_header() != attestation.get_block_hash().to_vec() {
+                return false
+            }
+        },
+        None => return false
+    };
+
+    // confirm the attestation is from a validator of the current epoch, validators on probation aren't part of the epoch
+    if !blockchain.get_current_epoch_validators().iter().any(|(validator_pub_key, _)| *validator_pub_key == attestation.get_public_key()) {
+        return false
+    }
+
+    let verifying_key = match VerifyingKey::from_sec1_bytes(&attestation.get_public_key()) {
+        Ok(verifying_key) => verifying_key,
+        // if an invalid public key is received then the attestation is invalid
+        Err(_) => return false
+    };
+
+    // verify the signature over the attested block height and hash
+    verify_sig(&verifying_key, &Attestation::serialize_hash_attestation_metadata(attestation.get_block_height(), attestation.get_block_hash()), &attestation.get_signature())
+}
+
+pub fn get_block_proposer(block: &Block, blockchain: &Blockchain) -> Option<([u8; COMPRESSED_PUBLIC_KEY_SIZE], u64)> {
+    // returns the public key of the validator that signed the block and the earliest timestamp they were allowed to propose it at
+
+    // get the contents of what the block signature should contain
+    let hashed_serialized_block_header = block.serialize_hash_block_signing_payload(blockchain.get_signed_chain_id());
+
+    // try each validator in the order they were chosen to propose this block
+    for (proposer_pub_key, earliest_timestamp) in blockchain.get_proposer_schedule() {
+        // verify the signature and message with the chosen validator's public key
+        if verify_signature(&proposer_pub_key, &hashed_serialized_block_header, &block.get_signature()) {
+            return Some((proposer_pub_key, earliest_timestamp))
+        }
+    }
+
+    // if all validators have been exhausted and the signature doesnt match then no validator proposed this block
+    None
+}
+
+pub fn get_block_proposer_public_key(block: &Block, blockchain: &Blockchain) -> Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> {
+    // returns the public key of the validator that signed the block, without searching the proposer schedule for blocks at or below the last trusted checkpoint
+    if is_below_trusted_checkpoint(blockchain) {
+        return recover_block_proposer(block, blockchain)
+    }
+
+    get_block_proposer(block, blockchain).map(|(proposer_pub_key, _)| proposer_pub_key)
+}
+
+fn is_below_trusted_checkpoint(blockchain: &Blockchain) -> bool {
+    // if the next block is at or below the last trusted checkpoint
+    blockchain.get_block_height() < blockchain.get_last_trusted_checkpoint_height()
+}
+
+fn recover_block_proposer(block: &Block, blockchain: &Blockchain) -> Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> {
+    // recover the public keys that could have created the block signature and use the one that belongs to a validator of the current epoch
+    let hashed_serialized_block_header = block.serialize_hash_block_signing_payload(blockchain.get_signed_chain_id());
+    let validators = blockchain.get_current_epoch_validators();
+
+    let signature = match block.get_signature() {
+        Signature::Ecdsa(signature) => signature,
+        // a public key can't be recovered from a Schnorr signature, verify it with each validator's public key instead
+        Signature::Schnorr(_) => return validators.iter()
+            .find(|(validator_pub_key, _)| verify_signature(validator_pub_key, &hashed_serialized_block_header, &block.get_signature()))
+            .map(|(validator_pub_key, _)| *validator_pub_key)
+    };
+
+    // public key recovery accepts high S signatures, only canonical low S signatures are valid
+    if !is_low_s(&signature) {
+        return None
+    }
+
+    for recovery_id in 0..=1 {
+        let recovered_key = match VerifyingKey::recover_from_msg(&hashed_serialized_block_header, &signature, RecoveryId::from_byte(recovery_id)?) {
+            Ok(recovered_key) => recovered_key,
+            Err(_) => continue
+        };
+
+        let proposer_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = recovered_key.to_encoded_point(true).as_bytes().try_into().ok()?;
+        if validators.iter().any(|(validator_pub_key, _)| *validator_pub_key == proposer_pub_key) {
+            return Some(proposer_pub_key)
+        }
+    }
+
+    None
+}
+
+pub fn is_coinbase(transaction: &Transaction, block: Option<&Block>, blockchain: &Blockchain) -> bool {
+    // the signature of a coinbase transaction only needs to be a valid signature, its contents are never checked
+
+    // if there is no block passed in, then it cannot be a valid coinbase transaction
     match block {
         Some(block) => {
             // make sure the transaction is the first transaction in the block
@@ -260,8 +715,8 @@ pub fn is_coinbase(transaction: &Transaction, block: Option<&Block>, block_heigh
         return false;
     }
 
-    // max block reward for the next block
-    let max_block_reward = get_block_subsidy(block_height + 1);
+    // max block reward for the next block, the block subsidy plus the loose change recycled into the block reward
+    let max_block_reward = get_block_subsidy(blockchain.get_block_height() + 1) + blockchain.get_loose_change_reward();
 
     // make sure the reward amount is less than the maximum reward amount for the current blockheight
     if transaction.amount > max_block_reward {
@@ -271,15 +726,17 @@ pub fn is_coinbase(transaction: &Transaction, block: Option<&Block>, block_heigh
     true
 }
 
-pub fn is_validator_enable(transaction: &Transaction, blockchain: &Blockchain) -> bool {
-    // get the public key from the transaction
-    let validator_pub_key = match PublicKey::from_sec1_bytes(&transaction.sender) {
-        Ok(validator_pub_key) => validator_pub_key,
-        Err(_) => return false
-    };
+pub fn is_validator_enable(transaction: &Transaction, block: Option<&Block>, blockchain: &Blockchain) -> bool {
+    // confirm the transaction doesn't carry any data
+    if transaction.data != TxData::None {
+        return false
+    }
 
-    // get the account address
-    let validator_address = Wallet::generate_address(&validator_pub_key, true);
+    // get the account address, its key may have been rotated
+    let validator_address = match blockchain.get_address(&transaction.sender) {
+        Some(validator_address) => validator_address,
+        None => return false
+    };
 
     // confirm user is not already a validator on chain
     match blockchain.get_account(&validator_address) {
@@ -304,18 +761,43 @@ pub fn is_validator_enable(transaction: &Transaction, blockchain: &Blockchain) -
         }
     }
 
+    // the genesis block's validators are exempt from the sybil protections below
+    if is_genesis_block(block) {
+        return true
+    }
+
+    // there is no stake required during the bootstrapping phase, instead the user must pay a minimum fee that is burned
+    if transaction.fee < blockchain.get_minimum_validator_enable_fee() {
+        return false
+    }
+
+    // confirm the block doesn't already have the maximum amount of validator enable transactions
+    if blockchain.get_validator_enables() >= *MAX_VALIDATOR_ENABLES_PER_BLOCK {
+        return false
+    }
+
     true
 }
 
+pub fn is_genesis_block(block: Option<&Block>) -> bool {
+    // the genesis block is the only block without a previous block
+    match block {
+        Some(block) => block.prev_hash() == [0x00; 32],
+        None => false
+    }
+}
+
 pub fn is_validator_revoke(transaction: &Transaction, blockchain: &Blockchain) -> bool {
-    // get the public key from the transaction
-    let validator_pub_key = match PublicKey::from_sec1_bytes(&transaction.sender) {
-        Ok(validator_pub_key) => validator_pub_key,
-        Err(_) => return false
-    };
+    // confirm the transaction doesn't carry any data
+    if transaction.data != TxData::None {
+        return false
+    }
 
-    // get the account address
-    let validator_address = Wallet::generate_address(&validator_pub_key, true);
+    // get the account address, its key may have been rotated
+    let validator_address = match blockchain.get_address(&transaction.sender) {
+        Some(validator_address) => validator_address,
+        None => return false
+    };
 
     // confirm user is already a validator on chain
     let validator_account = match blockchain.get_account(&validator_address) {
@@ -341,6 +823,468 @@ pub fn is_validator_revoke(transaction: &Transaction, blockchain: &Blockchain) -
     true
 }
 
+pub fn is_validator_stake_increase(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction doesn't carry any data
+    if transaction.data != TxData::None {
+        return false
+    }
+
+    // get the account address, its key may have been rotated
+    let validator_address = match blockchain.get_address(&transaction.sender) {
+        Some(validator_address) => validator_address,
+        None => return false
+    };
+
+    // confirm user is already a validator on chain, new validators must use a validator enable transaction
+    match blockchain.get_account(&validator_address) {
+        Some(validator_account) =>  {
+            if !validator_account.get_validator() {
+                return false
+            }
+        },
+        None => return false
+    };
+
+    // confirm the recipient is the standard VALIDATOR_STAKE_INCREASE_RECIPIENT address
+    if transaction.recipient != *VALIDATOR_STAKE_INCREASE_RECIPIENT {
+        return false
+    }
+
+    // confirm the validator is actually adding to their stake
+    if transaction.amount == 0 {
+        return false
+    }
+
+    true
+}
+
+pub fn is_validator_stake_withdraw(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction doesn't carry any data
+    if transaction.data != TxData::None {
+        return false
+    }
+
+    // get the account address, its key may have been rotated
+    let validator_address = match blockchain.get_address(&transaction.sender) {
+        Some(validator_address) => validator_address,
+        None => return false
+    };
+
+    // confirm user is already a validator on chain
+    let validator_account = match blockchain.get_account(&validator_address) {
+        Some(validator_account) =>  {
+            if !validator_account.get_validator() {
+                return false
+            }
+            validator_account
+        },
+        None => return false
+    };
+
+    // confirm the recipient is the standard VALIDATOR_STAKE_WITHDRAW_RECIPIENT address
+    if transaction.recipient != *VALIDATOR_STAKE_WITHDRAW_RECIPIENT {
+        return false
+    }
+
+    // confirm the validator is withdrawing some, but not all, of their stake. Withdrawing the entire stake must be done with a validator revoke transaction
+    if transaction.amount == 0 || transaction.amount >= validator_account.get_stake() {
+        return false
+    }
+
+    // confirm the remaining stake is still at least the minimum staking amount considering the bootstrapping phase
+    if blockchain.get_block_height() >= *BOOTSTRAPPING_PHASE_BLOCK_HEIGHT && validator_account.get_stake() - transaction.amount < *MINIMUM_STAKING_AMOUNT {
+        return false
+    }
+
+    true
+}
+
+pub fn is_delegate(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction is a delegation
+    if transaction.data != TxData::Delegate {
+        return false
+    }
+
+    // get the account address, its key may have been rotated
+    let delegator_address = match blockchain.get_address(&transaction.sender) {
+        Some(delegator_address) => delegator_address,
+        None => return false
+    };
+
+    // confirm the recipient is a current validator
+    if blockchain.get_validator_by_address(&transaction.recipient).is_none() {
+        return false
+    }
+
+    // validators add to their own stake with a validator stake increase transaction
+    if transaction.recipient == delegator_address {
+        return false
+    }
+
+    // confirm the delegator is actually delegating funds
+    if transaction.amount == 0 {
+        return false
+    }
+
+    true
+}
+
+pub fn is_batch(transaction: &Transaction) -> bool {
+    // confirm the recipient is the standard BATCH_RECIPIENT address and the transaction version supports outputs
+    if transaction.recipient != *BATCH_RECIPIENT || transaction.version < *BATCH_TRANSACTION_VERSION || transaction.data != TxData::None {
+        return false
+    }
+
+    // confirm the number of outputs is within bounds
+    if transaction.outputs.is_empty() || transaction.outputs.len() > MAX_BATCH_OUTPUTS {
+        return false
+    }
+
+    // confirm every output pays a positive amount to a regular address
+    if transaction.outputs.iter().any(|(recipient, amount)| *amount == 0 || is_special_recipient(recipient)) {
+        return false
+    }
+
+    // confirm the transaction amount is the total of the outputs
+    transaction.outputs.iter().try_fold(0u64, |total, (_, amount)| total.checked_add(*amount)) == Some(transaction.amount)
+}
+
+pub fn is_special_recipient(recipient: &[u8; BLOCK_ADDRESS_SIZE]) -> bool {
+    // the "special" addresses identify transaction types or hold protocol funds, no one can send a regular payment to them
+    [*VALIDATOR_ENABLE_RECIPIENT, *VALIDATOR_REVOKE_RECIPIENT, *VALIDATOR_STAKE_INCREASE_RECIPIENT, *VALIDATOR_STAKE_WITHDRAW_RECIPIENT, *VALIDATOR_COMMISSION_RECIPIENT, *BURN_RECIPIENT, *LOOSE_CHANGE_RECIPIENT, *BATCH_RECIPIENT, *HTLC_RECIPIENT, *CHANNEL_RECIPIENT, *KEY_ROTATION_RECIPIENT, *NAME_RECIPIENT].contains(recipient)
+}
+
+pub fn is_channel_open(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction opens a payment channel and the payment_channel deployment is active
+    if transaction.data != TxData::ChannelOpen || !blockchain.is_deployment_active("payment_channel") {
+        return false
+    }
+
+    // the payer signs channel updates with a single key, so multisig accounts can't open a channel
+    if transaction.multisig.is_some() {
+        return false
+    }
+
+    // confirm the channel pays a regular account other than the payer that can be spent from
+    if is_special_recipient(&transaction.recipient) || blockchain.is_rotated_key_address(&transaction.recipient) || blockchain.get_sender_address(transaction) == Some(transaction.recipient) {
+        return false
+    }
+
+    // confirm the payer is actually depositing funds
+    if transaction.amount == 0 {
+        return false
+    }
+
+    true
+}
+
+pub fn is_channel_close(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction closes a payment channel with the standard CHANNEL_RECIPIENT address and the payment_channel deployment is active
+    let channel_update = match transaction.data {
+        TxData::ChannelClose(channel_update) if blockchain.is_deployment_active("payment_channel") && transaction.recipient == *CHANNEL_RECIPIENT => channel_update,
+        _ => return false
+    };
+
+    // the settled amounts come from the channel deposit
+    if transaction.amount != 0 {
+        return false
+    }
+
+    // confirm the update was signed by the payer of an open channel
+    if !verify_channel_update(&channel_update, blockchain) {
+        return false
+    }
+
+    let channel = match blockchain.get_payment_channel(&channel_update.get_channel_id()) {
+        Some(channel) => channel,
+        None => return false
+    };
+
+    // the payee can always close the channel, the payer can only close it once, after which the payee has the dispute period to close it with a later update
+    match blockchain.get_sender_address(transaction) {
+        Some(sender_address) if sender_address == channel.get_payee() => true,
+        Some(sender_address) if sender_address == channel.get_payer() => channel.get_settle_height().is_none(),
+        _ => false
+    }
+}
+
+pub fn verify_channel_update(channel_update: &ChannelUpdate, blockchain: &Blockchain) -> bool {
+    // confirm the channel is open
+    let channel = match blockchain.get_payment_channel(&channel_update.get_channel_id()) {
+        Some(channel) => channel,
+        None => return false
+    };
+
+    // confirm the update doesn't pay more than the deposit
+    if channel_update.get_paid() > channel.get_deposit() {
+        return false
+    }
+
+    // the payer signs updates with their account's current key, which is the key the channel was opened with unless it has been rotated since
+    let payer_public_key = blockchain.get_account(&channel.get_payer()).and_then(|payer_account| payer_account.get_public_key()).unwrap_or(channel.get_payer_public_key());
+
+    // confirm the update is only signed with Schnorr once the schnorr deployment is active
+    if !channel_update.get_signature().is_ecdsa() && !blockchain.is_deployment_active("schnorr") {
+        return false
+    }
+
+    // confirm the update was signed by the payer for this network
+    let update_payload = ChannelUpdate::serialize_hash_update_payload(channel_update.get_channel_id(), channel_update.get_paid(), blockchain.get_signed_chain_id());
+    verify_signature(&payer_public_key, &update_payload, &channel_update.get_signature())
+}
+
+pub fn is_key_rotation(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction rotates a key with the standard KEY_ROTATION_RECIPIENT address and the key_rotation deployment is active
+    let new_public_key = match transaction.data {
+        TxData::KeyRotation(new_public_key) if blockchain.is_deployment_active("key_rotation") && transaction.recipient == *KEY_ROTATION_RECIPIENT => new_public_key,
+        _ => return false
+    };
+
+    // multisig accounts are identified by their keys, so they can't be rotated
+    if transaction.multisig.is_some() {
+        return false
+    }
+
+    // confirm no funds are being sent
+    if transaction.amount != 0 {
+        return false
+    }
+
+    // confirm the new key is different from the key signing the transaction
+    if new_public_key == transaction.sender {
+        return false
+    }
+
+    // get the address of the account being rotated
+    let account_address = match blockchain.get_sender_address(transaction) {
+        Some(account_address) => account_address,
+        None => return false
+    };
+
+    // confirm the new key is a valid public key that doesn't control another account, rotating back to the key the address was generated from is allowed
+    match blockchain.get_address(&new_public_key) {
+        Some(new_address) if new_address == account_address => true,
+        Some(new_address) => blockchain.get_account(&new_address).is_none(),
+        None => false
+    }
+}
+
+pub fn is_name_registration(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction registers a name with the standard NAME_RECIPIENT address and the name_registry deployment is active
+    let name = match transaction.data {
+        TxData::NameRegister(name) if blockchain.is_deployment_active("name_registry") && transaction.recipient == *NAME_RECIPIENT => name,
+        _ => return false
+    };
+
+    // confirm the name is made up of allowed characters
+    if !NameRecord::is_valid_name(&name) {
+        return false
+    }
+
+    // confirm the sender burns at least the registration fee
+    if transaction.amount < *NAME_REGISTRATION_FEE {
+        return false
+    }
+
+    // confirm the name isn't registered yet or its registration has expired, the block the transaction is added to is at block_height + 1
+    match blockchain.get_name_record(&name) {
+        Some(name_record) => name_record.is_expired(blockchain.get_block_height() + 1),
+        None => true
+    }
+}
+
+pub fn is_name_renewal(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction renews a name with the standard NAME_RECIPIENT address and the name_registry deployment is active
+    let name = match transaction.data {
+        TxData::NameRenew(name) if blockchain.is_deployment_active("name_registry") && transaction.recipient == *NAME_RECIPIENT => name,
+        _ => return false
+    };
+
+    // confirm the sender burns at least the registration fee
+    if transaction.amount < *NAME_REGISTRATION_FEE {
+        return false
+    }
+
+    // confirm the sender owns the name, an expired name can be renewed by its owner until someone else registers it
+    match blockchain.get_name_record(&name) {
+        Some(name_record) => blockchain.get_sender_address(transaction) == Some(name_record.get_owner()),
+        None => false
+    }
+}
+
+pub fn is_name_transfer(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction transfers a name and the name_registry deployment is active
+    let name = match transaction.data {
+        TxData::NameTransfer(name) if blockchain.is_deployment_active("name_registry") => name,
+        _ => return false
+    };
+
+    // confirm no funds are being sent
+    if transaction.amount != 0 {
+        return false
+    }
+
+    // confirm the name is transferred to a regular account that can be spent from
+    if is_special_recipient(&transaction.recipient) || blockchain.is_rotated_key_address(&transaction.recipient) {
+        return false
+    }
+
+    // confirm the sender owns the name, its registration hasn't expired and it isn't transferring the name to itself
+    match blockchain.get_name_record(&name) {
+        Some(name_record) => {
+            let owner = name_record.get_owner();
+            blockchain.get_sender_address(transaction) == Some(owner) && owner != transaction.recipient && !name_record.is_expired(blockchain.get_block_height() + 1)
+        },
+        None => false
+    }
+}
+
+pub fn is_htlc_lock(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction locks funds and the htlc deployment is active
+    let refund_height = match transaction.data {
+        TxData::HtlcLock { refund_height, .. } if blockchain.is_deployment_active("htlc") => refund_height,
+        _ => return false
+    };
+
+    // confirm the funds can be claimed by a regular account that can be spent from
+    if is_special_recipient(&transaction.recipient) || blockchain.is_rotated_key_address(&transaction.recipient) {
+        return false
+    }
+
+    // confirm the sender is actually locking funds
+    if transaction.amount == 0 {
+        return false
+    }
+
+    // confirm the funds can be claimed in at least one block, the block the transaction is added to is at block_height + 1
+    refund_height > blockchain.get_block_height() + 1
+}
+
+pub fn is_htlc_claim(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction claims a transfer with the standard HTLC_RECIPIENT address and the htlc deployment is active
+    let (lock_id, preimage) = match transaction.data {
+        TxData::HtlcClaim { lock_id, preimage } if blockchain.is_deployment_active("htlc") && transaction.recipient == *HTLC_RECIPIENT => (lock_id, preimage),
+        _ => return false
+    };
+
+    // the claimed amount comes from the transfer
+    if transaction.amount != 0 {
+        return false
+    }
+
+    // confirm the transfer hasn't been claimed or refunded yet
+    let htlc = match blockchain.get_htlc(&lock_id) {
+        Some(htlc) => htlc,
+        None => return false
+    };
+
+    // confirm the transfer is claimed by its recipient
+    if blockchain.get_sender_address(transaction) != Some(htlc.get_recipient()) {
+        return false
+    }
+
+    // confirm the preimage unlocks the transfer
+    if !htlc.is_unlocked_by(&preimage) {
+        return false
+    }
+
+    // confirm the transfer can still be claimed, the block the transaction is added to is at block_height + 1
+    blockchain.get_block_height() + 1 < htlc.get_refund_height()
+}
+
+pub fn is_htlc_refund(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction refunds a transfer with the standard HTLC_RECIPIENT address and the htlc deployment is active
+    let lock_id = match transaction.data {
+        TxData::HtlcRefund { lock_id } if blockchain.is_deployment_active("htlc") && transaction.recipient == *HTLC_RECIPIENT => lock_id,
+        _ => return false
+    };
+
+    // the refunded amount comes from the transfer
+    if transaction.amount != 0 {
+        return false
+    }
+
+    // confirm the transfer hasn't been claimed or refunded yet
+    let htlc = match blockchain.get_htlc(&lock_id) {
+        Some(htlc) => htlc,
+        None => return false
+    };
+
+    // confirm the transfer is refunded to its sender
+    if blockchain.get_sender_address(transaction) != Some(htlc.get_sender()) {
+        return false
+    }
+
+    // confirm the transfer has expired, the block the transaction is added to is at block_height + 1
+    blockchain.get_block_height() + 1 >= htlc.get_refund_height()
+}
+
+pub fn is_undelegate(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction is an undelegation
+    if transaction.data != TxData::Undelegate {
+        return false
+    }
+
+    // get the account address, its key may have been rotated
+    let delegator_address = match blockchain.get_address(&transaction.sender) {
+        Some(delegator_address) => delegator_address,
+        None => return false
+    };
+
+    // confirm the recipient is a current validator
+    let validator = match blockchain.get_validator_by_address(&transaction.recipient) {
+        Some(validator) => validator,
+        None => return false
+    };
+
+    // confirm the delegator is undelegating some amount, but no more than they have delegated to this validator
+    if transaction.amount == 0 || transaction.amount > validator.get_delegation(&delegator_address) {
+        return false
+    }
+
+    true
+}
+
+pub fn is_validator_commission(transaction: &Transaction, blockchain: &Blockchain) -> bool {
+    // confirm the transaction sets a validator commission and the commission is at most 100%
+    match transaction.data {
+        TxData::ValidatorCommission(commission) => {
+            if commission > *MAX_VALIDATOR_COMMISSION {
+                return false
+            }
+        },
+        _ => return false
+    }
+
+    // get the account address, its key may have been rotated
+    let validator_address = match blockchain.get_address(&transaction.sender) {
+        Some(validator_address) => validator_address,
+        None => return false
+    };
+
+    // confirm user is already a validator on chain
+    match blockchain.get_account(&validator_address) {
+        Some(validator_account) =>  {
+            if !validator_account.get_validator() {
+                return false
+            }
+        },
+        None => return false
+    };
+
+    // confirm the recipient is the standard VALIDATOR_COMMISSION_RECIPIENT address
+    if transaction.recipient != *VALIDATOR_COMMISSION_RECIPIENT {
+        return false
+    }
+
+    // a commission transaction doesn't move any funds
+    if transaction.amount != 0 {
+        return false
+    }
+
+    true
+}
+
 pub fn get_block_subsidy(block_height: u64) -> u64 {
     // taken straight from bitcoin's codebase : )
     let halvings = block_height / HALVING_INTERVAL;
@@ -0,0 +1,128 @@
+use elliptic_curve::{ff::PrimeField, ops::Reduce, sec1::{FromEncodedPoint, ToEncodedPoint}};
+use k256::{ecdsa::SigningKey, AffinePoint, EncodedPoint, ProjectivePoint, Scalar, U256};
+use sha2::{Sha256, Digest};
+
+use crate::constants::{COMPRESSED_PUBLIC_KEY_SIZE, VRF_PROOF_SIZE};
+
+// verifiable random function used by block proposers to produce the randomness beacon
+// this follows the construction of ECVRF (RFC 9381) over secp256k1 with try and increment hashing to the curve
+// the proof is gamma (33 bytes compressed point) + c (32 byte scalar) + s (32 byte scalar)
+// a validator can only produce one valid proof for a given input, so the output can't be ground by the proposer
+
+// domain separation for all hashes used by the vrf
+const VRF_SUITE: &[u8] = b"BLOCK-ECVRF-SECP256K1-SHA256-TAI";
+
+pub fn prove(signing_key: &SigningKey, alpha: &[u8]) -> Option<[u8; VRF_PROOF_SIZE]> {
+    // secret scalar x and public key Y = x*G
+    let secret: Scalar = *signing_key.as_nonzero_scalar().as_ref();
+    let public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = signing_key.verifying_key().to_encoded_point(true).as_bytes().try_into().ok()?;
+
+    // H = hash_to_curve(Y, alpha), gamma = x*H
+    let h = hash_to_curve(&public_key, alpha)?;
+    let gamma = h * secret;
+
+    // deterministic nonce k = sha256(suite + x + H), never reusing a nonce for a different H
+    let mut sha256_hasher: Sha256 = Sha256::new();
+    sha256_hasher.update(VRF_SUITE);
+    sha256_hasher.update(secret.to_bytes());
+    sha256_hasher.update(encode_point(&h));
+    let k = <Scalar as Reduce<U256>>::reduce_bytes(&sha256_hasher.finalize());
+
+    // c = hash_points(H, gamma, k*G, k*H), s = k + c*x
+    let c = hash_points(&h, &gamma, &(ProjectivePoint::GENERATOR * k), &(h * k));
+    let s = k + c * secret;
+
+    // proof = gamma + c + s
+    let mut proof = [0x00; VRF_PROOF_SIZE];
+    proof[..33].copy_from_slice(&encode_point(&gamma));
+    proof[33..65].copy_from_slice(&c.to_bytes());
+    proof[65..].copy_from_slice(&s.to_bytes());
+
+    Some(proof)
+}
+
+pub fn verify(public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE], alpha: &[u8], proof: &[u8; VRF_PROOF_SIZE]) -> Option<[u8; 32]> {
+    // returns the vrf output if the proof is valid for the public key and input
+    let y = decode_point(public_key)?;
+    let (gamma, c, s) = decode_proof(proof)?;
+
+    // H = hash_to_curve(Y, alpha)
+    let h = hash_to_curve(public_key, alpha)?;
+
+    // U = s*G - c*Y, V = s*H - c*gamma
+    let u = ProjectivePoint::GENERATOR * s - y * c;
+    let v = h * s - gamma * c;
+
+    // the proof is valid if c = hash_points(H, gamma, U, V)
+    if hash_points(&h, &gamma, &u, &v) != c {
+        return None
+    }
+
+    proof_to_hash(proof)
+}
+
+pub fn proof_to_hash(proof: &[u8; VRF_PROOF_SIZE]) -> Option<[u8; 32]> {
+    // the vrf output is sha256(suite + 0x03 + gamma), this does NOT verify the proof
+    let (gamma, _, _) = decode_proof(proof)?;
+
+    let mut sha256_hasher: Sha256 = Sha256::new();
+    sha256_hasher.update(VRF_SUITE);
+    sha256_hasher.update([0x03]);
+    sha256_hasher.update(encode_point(&gamma));
+    Some(sha256_hasher.finalize().into())
+}
+
+fn hash_to_curve(public_key: &[u8; COMPRESSED_PUBLIC_KEY_SIZE], alpha: &[u8]) -> Option<ProjectivePoint> {
+    // try and increment, hash the public key, input and a counter until the hash is a valid x coordinate
+    for counter in 0..=u8::MAX {
+        let mut sha256_hasher: Sha256 = Sha256::new();
+        sha256_hasher.update(VRF_SUITE);
+        sha256_hasher.update([0x01]);
+        sha256_hasher.update(public_key);
+        sha256_hasher.update(alpha);
+        sha256_hasher.update([counter]);
+
+        // interpret the hash as the x coordinate of a compressed point with an even y coordinate
+        let mut encoded_point = [0x02; COMPRESSED_PUBLIC_KEY_SIZE];
+        encoded_point[1..].copy_from_slice(&sha256_hasher.finalize());
+
+        if let Some(point) = decode_point(&encoded_point) {
+            return Some(point)
+        }
+    }
+
+    // roughly half of all hashes are valid x coordinates, this should never happen
+    None
+}
+
+fn hash_points(h: &ProjectivePoint, gamma: &ProjectivePoint, u: &ProjectivePoint, v: &ProjectivePoint) -> Scalar {
+    // c = sha256(suite + 0x02 + H + gamma + U + V) mod n
+    let mut sha256_hasher: Sha256 = Sha256::new();
+    sha256_hasher.update(VRF_SUITE);
+    sha256_hasher.update([0x02]);
+    for point in [h, gamma, u, v] {
+        sha256_hasher.update(encode_point(point));
+    }
+    <Scalar as Reduce<U256>>::reduce_bytes(&sha256_hasher.finalize())
+}
+
+fn decode_proof(proof: &[u8; VRF_PROOF_SIZE]) -> Option<(ProjectivePoint, Scalar, Scalar)> {
+    // split the proof into gamma, c and s, the scalars must be in their canonical form
+    let gamma = decode_point(&proof[..33])?;
+    let c_bytes: [u8; 32] = proof[33..65].try_into().ok()?;
+    let s_bytes: [u8; 32] = proof[65..].try_into().ok()?;
+    let c = Option::from(Scalar::from_repr(c_bytes.into()))?;
+    let s = Option::from(Scalar::from_repr(s_bytes.into()))?;
+
+    Some((gamma, c, s))
+}
+
+fn decode_point(bytes: &[u8]) -> Option<ProjectivePoint> {
+    let encoded_point = EncodedPoint::from_bytes(bytes).ok()?;
+    let point: Option<AffinePoint> = AffinePoint::from_encoded_point(&encoded_point).into();
+    point.map(ProjectivePoint::from)
+}
+
+fn encode_point(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
\ No newline at end of file
@@ -3,17 +3,24 @@ use std::io::{self, Write};
 use std::path::Path;
 
 use elliptic_curve::{SecretKey, sec1::ToEncodedPoint};
-use k256::{ecdsa::{SigningKey, Signature, signature::Signer}, PublicKey, Secp256k1};
-use rand_core::OsRng;
+use k256::{ecdsa::{self, SigningKey, signature::Signer}, schnorr, PublicKey, Secp256k1};
+use rand_core::{OsRng, RngCore};
 use ripemd::Ripemd160;
 use sha2::{Sha256, Digest};
 
+use crate::attestation::Attestation;
 use crate::block::{Block, BlockHeader};
+use crate::channel_update::ChannelUpdate;
 use crate::config::WalletConfig;
-use crate::transaction::{Transaction, TxMetadata};
+use crate::multisig::Multisig;
+use crate::signature::Signature;
+use crate::transaction::{Transaction, TxData, TxMetadata};
+use crate::tx_format::TxFormat;
+use crate::tx_options::TxOptions;
+use crate::vrf;
 use crate::util::{open_file_read, create_file_new, read_file_from_beginning, open_file_write};
 
-use crate::constants::{BLOCK_ADDRESS_VERSION1_BYTES, WIF_VERSION1_PREFIX_BYTES, WIF_VERSION1_COMPRESSED_BYTES, TRANSACTION_VERSION, BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE, COINBASE_SENDER, VALIDATOR_ENABLE_RECIPIENT, VALIDATOR_REVOKE_RECIPIENT};
+use crate::constants::{BLOCK_ADDRESS_VERSION1_BYTES, WIF_VERSION1_PREFIX_BYTES, WIF_VERSION1_COMPRESSED_BYTES, TRANSACTION_VERSION, DATA_TRANSACTION_VERSION, MULTISIG_TRANSACTION_VERSION, VALIDITY_WINDOW_TRANSACTION_VERSION, MEMO_TRANSACTION_VERSION, BATCH_TRANSACTION_VERSION, SCHNORR_TRANSACTION_VERSION, HTLC_TRANSACTION_VERSION, CHANNEL_TRANSACTION_VERSION, KEY_ROTATION_TRANSACTION_VERSION, NAME_TRANSACTION_VERSION, SCHNORR_BLOCK_VERSION, BLOCK_ADDRESS_SIZE, COMPRESSED_PUBLIC_KEY_SIZE, MAX_NAME_SIZE, NAME_REGISTRATION_FEE, COINBASE_SENDER, MULTISIG_SENDER, BATCH_RECIPIENT, CHANNEL_RECIPIENT, HTLC_RECIPIENT, KEY_ROTATION_RECIPIENT, NAME_RECIPIENT, VALIDATOR_ENABLE_RECIPIENT, VALIDATOR_REVOKE_RECIPIENT, VALIDATOR_STAKE_INCREASE_RECIPIENT, VALIDATOR_STAKE_WITHDRAW_RECIPIENT, VALIDATOR_COMMISSION_RECIPIENT, VRF_PROOF_SIZE};
 
 #[derive(Clone)]
 pub struct Wallet {
@@ -41,17 +48,20 @@ impl Wallet {
             }
         };
 
-        // read private key and nonce from wallet key file
-        let (private_key, nonce) = match Self::read_wallet_file(wallet_file, config.get_compressed_public_key()) {
+        // read private key, nonce and the account address of a rotated key from wallet key file
+        let (private_key, nonce, account_address) = match Self::read_wallet_file(wallet_file, config.get_compressed_public_key()) {
             Some(wallet_file_tuple) => wallet_file_tuple,
             None => {
                 panic!("Error reading wallet file");
             }
         };
 
-        // obtain public key and address
+        // obtain public key and address, a rotated key controls the account it was rotated to instead of the address generated from it
         let public_key: elliptic_curve::PublicKey<Secp256k1> = private_key.public_key();
-        let address: [u8; BLOCK_ADDRESS_SIZE] = Wallet::generate_address(&public_key, config.get_compressed_public_key());
+        let address: [u8; BLOCK_ADDRESS_SIZE] = match account_address {
+            Some(account_address) => account_address,
+            None => Wallet::generate_address(&public_key, config.get_compressed_public_key())
+        };
         
         Self {
             public_key,
@@ -89,9 +99,18 @@ impl Wallet {
         self.update_wallet_file();
     }
 
-    pub fn create_tx(&mut self, amount: u64, fee: u64, recipient: [u8; BLOCK_ADDRESS_SIZE]) -> Option<Transaction> {
+    pub fn create_tx(&mut self, amount: u64, fee: u64, recipient: [u8; BLOCK_ADDRESS_SIZE], tx_options: TxOptions, tx_format: TxFormat) -> Option<Transaction> {
+        // use the oldest transaction version that supports the transaction's optional fields
+        let version = tx_format.get_version(if !tx_options.get_memo().is_empty() {
+            *MEMO_TRANSACTION_VERSION
+        } else if tx_options.has_validity_window() {
+            *VALIDITY_WINDOW_TRANSACTION_VERSION
+        } else {
+            *TRANSACTION_VERSION
+        });
+
         // get the signature for the transaction
-        let tx_sig = match Self::create_tx_sig(self, *TRANSACTION_VERSION, amount, fee, recipient, self.nonce) {
+        let tx_sig = match Self::create_tx_sig(self, &TxMetadata::new(version, amount, fee, recipient, self.nonce).with_sender(self.address).with_validity_window(tx_options.get_valid_after(), tx_options.get_valid_until()).with_memo(tx_options.get_memo()).with_chain_id(tx_format.get_chain_id()), tx_format) {
             Some(tx_sig) => tx_sig,
             None => return None
         };
@@ -101,14 +120,14 @@ impl Wallet {
         let sender_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = sender_pub_key_vec.try_into().unwrap();
         
         // create the transaction
-        let tx = Transaction::new(*TRANSACTION_VERSION, amount, fee, recipient, sender_pub_key, tx_sig, self.nonce);
+        let tx = Transaction::new(version, amount, fee, recipient, sender_pub_key, tx_sig, self.nonce).with_validity_window(tx_options.get_valid_after(), tx_options.get_valid_until()).with_memo(tx_options.get_memo());
 
         Some(tx)
     }
 
     pub fn create_coinbase_tx(&mut self, amount: u64, recipient: [u8; BLOCK_ADDRESS_SIZE]) -> Option<Transaction> {
         // get the signature for the coinbase transaction
-        let tx_sig = match Self::create_tx_sig(self, *TRANSACTION_VERSION, amount, 0, recipient, 0) {
+        let tx_sig = match Self::create_ecdsa_tx_sig(self, &TxMetadata::new(*TRANSACTION_VERSION, amount, 0, recipient, 0).with_sender(self.address)).map(Signature::Ecdsa) {
             Some(tx_sig) => tx_sig,
             None => return None
         };
@@ -122,8 +141,9 @@ impl Wallet {
         Some(tx)
     }
 
-    pub fn create_validator_enable_tx(&mut self, amount: u64, fee: u64) -> Option<Transaction> {
-        let tx_sig = match Self::create_tx_sig(self, *TRANSACTION_VERSION, amount, fee, *VALIDATOR_ENABLE_RECIPIENT, self.nonce) {
+    pub fn create_validator_enable_tx(&mut self, amount: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        let version = tx_format.get_version(*TRANSACTION_VERSION);
+        let tx_sig = match Self::create_tx_sig(self, &TxMetadata::new(version, amount, fee, *VALIDATOR_ENABLE_RECIPIENT, self.nonce).with_sender(self.address).with_chain_id(tx_format.get_chain_id()), tx_format) {
             Some(tx_sig) => tx_sig,
             None => return None
         };
@@ -132,13 +152,14 @@ impl Wallet {
         let sender_pub_key_vec = self.get_public_key().to_sec1_bytes().to_vec();
         let sender_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = sender_pub_key_vec.try_into().unwrap();
 
-        let tx = Transaction::new(*TRANSACTION_VERSION, amount, fee, *VALIDATOR_ENABLE_RECIPIENT, sender_pub_key, tx_sig, self.nonce);
+        let tx = Transaction::new(version, amount, fee, *VALIDATOR_ENABLE_RECIPIENT, sender_pub_key, tx_sig, self.nonce);
 
         Some(tx)
     }
 
-    pub fn create_validator_revoke_tx(&mut self, amount: u64, fee: u64) -> Option<Transaction> {
-        let tx_sig = match Self::create_tx_sig(self, *TRANSACTION_VERSION, amount, fee, *VALIDATOR_REVOKE_RECIPIENT, self.nonce) {
+    pub fn create_validator_revoke_tx(&mut self, amount: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        let version = tx_format.get_version(*TRANSACTION_VERSION);
+        let tx_sig = match Self::create_tx_sig(self, &TxMetadata::new(version, amount, fee, *VALIDATOR_REVOKE_RECIPIENT, self.nonce).with_sender(self.address).with_chain_id(tx_format.get_chain_id()), tx_format) {
             Some(tx_sig) => tx_sig,
             None => return None
         };
@@ -147,12 +168,250 @@ impl Wallet {
         let sender_pub_key_vec = self.get_public_key().to_sec1_bytes().to_vec();
         let sender_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = sender_pub_key_vec.try_into().unwrap();
 
-        let tx = Transaction::new(*TRANSACTION_VERSION, amount, fee, *VALIDATOR_REVOKE_RECIPIENT, sender_pub_key, tx_sig, self.nonce);
+        let tx = Transaction::new(version, amount, fee, *VALIDATOR_REVOKE_RECIPIENT, sender_pub_key, tx_sig, self.nonce);
 
         Some(tx)
     }
 
-    fn create_tx_sig(&self, version: u8, amount: u64, fee: u64, recipient: [u8; BLOCK_ADDRESS_SIZE], nonce: u64) -> Option<Signature> {
+    pub fn create_validator_stake_increase_tx(&mut self, amount: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a stake increase is a transaction from the validator to the VALIDATOR_STAKE_INCREASE_RECIPIENT
+        self.create_tx(amount, fee, *VALIDATOR_STAKE_INCREASE_RECIPIENT, TxOptions::new(), tx_format)
+    }
+
+    pub fn create_validator_stake_withdraw_tx(&mut self, amount: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a stake withdraw is a transaction from the validator to the VALIDATOR_STAKE_WITHDRAW_RECIPIENT
+        self.create_tx(amount, fee, *VALIDATOR_STAKE_WITHDRAW_RECIPIENT, TxOptions::new(), tx_format)
+    }
+
+    pub fn create_delegate_tx(&mut self, validator_address: [u8; BLOCK_ADDRESS_SIZE], amount: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a delegation is a transaction to the validator's address carrying delegate data
+        self.create_data_tx(*DATA_TRANSACTION_VERSION, amount, fee, validator_address, TxData::Delegate, tx_format)
+    }
+
+    pub fn create_undelegate_tx(&mut self, validator_address: [u8; BLOCK_ADDRESS_SIZE], amount: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // an undelegation is a transaction to the validator's address carrying undelegate data
+        self.create_data_tx(*DATA_TRANSACTION_VERSION, amount, fee, validator_address, TxData::Undelegate, tx_format)
+    }
+
+    pub fn create_validator_commission_tx(&mut self, commission: u16, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a commission change is a transaction from the validator to the VALIDATOR_COMMISSION_RECIPIENT carrying the new commission
+        self.create_data_tx(*DATA_TRANSACTION_VERSION, 0, fee, *VALIDATOR_COMMISSION_RECIPIENT, TxData::ValidatorCommission(commission), tx_format)
+    }
+
+    pub fn create_htlc_lock_tx(&mut self, recipient: [u8; BLOCK_ADDRESS_SIZE], amount: u64, hash_lock: [u8; 32], refund_height: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a lock is a transaction to the address that can claim the funds carrying the hash lock and refund height
+        self.create_data_tx(*HTLC_TRANSACTION_VERSION, amount, fee, recipient, TxData::HtlcLock { hash_lock, refund_height }, tx_format)
+    }
+
+    pub fn create_htlc_claim_tx(&mut self, lock_id: [u8; 32], preimage: [u8; 32], fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a claim is a transaction from the transfer's recipient to the HTLC_RECIPIENT carrying the preimage
+        self.create_data_tx(*HTLC_TRANSACTION_VERSION, 0, fee, *HTLC_RECIPIENT, TxData::HtlcClaim { lock_id, preimage }, tx_format)
+    }
+
+    pub fn create_htlc_refund_tx(&mut self, lock_id: [u8; 32], fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a refund is a transaction from the transfer's sender to the HTLC_RECIPIENT
+        self.create_data_tx(*HTLC_TRANSACTION_VERSION, 0, fee, *HTLC_RECIPIENT, TxData::HtlcRefund { lock_id }, tx_format)
+    }
+
+    pub fn create_channel_open_tx(&mut self, payee: [u8; BLOCK_ADDRESS_SIZE], deposit: u64, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // opening a channel is a transaction to the payee carrying the deposit
+        self.create_data_tx(*CHANNEL_TRANSACTION_VERSION, deposit, fee, payee, TxData::ChannelOpen, tx_format)
+    }
+
+    pub fn create_channel_close_tx(&mut self, channel_update: ChannelUpdate, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // closing a channel is a transaction from the payer or the payee to the CHANNEL_RECIPIENT carrying the update to settle with
+        self.create_data_tx(*CHANNEL_TRANSACTION_VERSION, 0, fee, *CHANNEL_RECIPIENT, TxData::ChannelClose(channel_update), tx_format)
+    }
+
+    pub fn create_channel_update(&self, channel_id: [u8; 32], paid: u64, tx_format: TxFormat) -> Option<ChannelUpdate> {
+        // obtain the wallet signing key from the wallet file
+        let signing_key = match self.get_signing_key() {
+            Some(signing_key) => signing_key,
+            None => {
+                println!("Failed to create channel update, could not obtain signing key");
+                return None
+            }
+        };
+
+        // channel updates are signed with Schnorr once the schnorr deployment is active, before that with ECDSA
+        let update_payload = ChannelUpdate::serialize_hash_update_payload(channel_id, paid, tx_format.get_chain_id());
+        let signature = if tx_format.is_schnorr_active() {
+            Signature::Schnorr(Self::sign_schnorr(signing_key, &update_payload)?)
+        } else {
+            Signature::Ecdsa(Self::sign(signing_key, &update_payload))
+        };
+
+        Some(ChannelUpdate::new(channel_id, paid, signature))
+    }
+
+    pub fn create_key_rotation_tx(&mut self, new_public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE], fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a key rotation is a transaction signed by the current key to the KEY_ROTATION_RECIPIENT carrying the new public key
+        self.create_data_tx(*KEY_ROTATION_TRANSACTION_VERSION, 0, fee, *KEY_ROTATION_RECIPIENT, TxData::KeyRotation(new_public_key), tx_format)
+    }
+
+    pub fn create_name_register_tx(&mut self, name: [u8; MAX_NAME_SIZE], fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a registration is a transaction to the NAME_RECIPIENT burning the registration fee and carrying the name
+        self.create_data_tx(*NAME_TRANSACTION_VERSION, *NAME_REGISTRATION_FEE, fee, *NAME_RECIPIENT, TxData::NameRegister(name), tx_format)
+    }
+
+    pub fn create_name_renew_tx(&mut self, name: [u8; MAX_NAME_SIZE], fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a renewal is a transaction from the name's owner to the NAME_RECIPIENT burning the registration fee and carrying the name
+        self.create_data_tx(*NAME_TRANSACTION_VERSION, *NAME_REGISTRATION_FEE, fee, *NAME_RECIPIENT, TxData::NameRenew(name), tx_format)
+    }
+
+    pub fn create_name_transfer_tx(&mut self, name: [u8; MAX_NAME_SIZE], recipient: [u8; BLOCK_ADDRESS_SIZE], fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a transfer is a transaction from the name's owner to its new owner carrying the name
+        self.create_data_tx(*NAME_TRANSACTION_VERSION, 0, fee, recipient, TxData::NameTransfer(name), tx_format)
+    }
+
+    pub fn generate_rotation_key(&self) -> (String, [u8; COMPRESSED_PUBLIC_KEY_SIZE]) {
+        // generate a new private key for the account to be rotated to, returned as a WIF string together with its public key
+        let private_key: elliptic_curve::SecretKey<Secp256k1> = SecretKey::random(&mut OsRng);
+        let wif_private_key = String::from_utf8(Self::generate_wif_private_key(&private_key, self.config.get_compressed_public_key())).unwrap();
+        let public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = private_key.public_key().to_encoded_point(true).as_bytes().try_into().unwrap();
+
+        (wif_private_key, public_key)
+    }
+
+    pub fn get_wif_public_key(&self, wif_private_key_string: &str) -> Option<[u8; COMPRESSED_PUBLIC_KEY_SIZE]> {
+        // obtain the compressed public key of a WIF private key
+        let private_key = self.parse_wif_private_key(wif_private_key_string)?;
+        private_key.public_key().to_encoded_point(true).as_bytes().try_into().ok()
+    }
+
+    fn parse_wif_private_key(&self, wif_private_key_string: &str) -> Option<elliptic_curve::SecretKey<Secp256k1>> {
+        // a WIF private key entered by the user may have typos, it is only valid if encoding it again gives the same string (including the checksum)
+        let private_key = Self::wif_to_private_key(wif_private_key_string, self.config.get_compressed_public_key())?;
+        if Self::generate_wif_private_key(&private_key, self.config.get_compressed_public_key()) != wif_private_key_string.as_bytes() {
+            return None
+        }
+
+        Some(private_key)
+    }
+
+    pub fn import_key(&mut self, wif_private_key_string: &str, account_address: [u8; BLOCK_ADDRESS_SIZE]) -> Option<()> {
+        // replace the wallet key with a key the account has been rotated to
+        let private_key = match self.parse_wif_private_key(wif_private_key_string) {
+            Some(private_key) => private_key,
+            None => {
+                println!("Failed to import key, invalid private key");
+                return None
+            }
+        };
+
+        self.public_key = private_key.public_key();
+        self.address = account_address;
+
+        // open wallet file in write mode
+        let wallet_file = match Self::open_wallet_file_write(self.config.get_wallet_file()) {
+            Some(wallet_file) => wallet_file,
+            None => {
+                println!("Failed to import key, could not write to wallet file");
+                return None
+            }
+        };
+
+        // write the imported private key, nonce and the account address it controls to the wallet file
+        match Self::write_to_wallet_file(&wallet_file, &private_key, self.get_nonce(), self.get_rotated_account_address(), self.config.get_compressed_public_key(), self.config.get_wallet_file_version()) {
+            Ok(_) => Some(()),
+            Err(_) => {
+                println!("Failed to import key, could not write to wallet file");
+                None
+            }
+        }
+    }
+
+    fn get_rotated_account_address(&self) -> Option<[u8; BLOCK_ADDRESS_SIZE]> {
+        // the wallet only needs to store its account address if the address wasn't generated from its key
+        if self.address == Self::generate_address(&self.public_key, self.config.get_compressed_public_key()) {
+            None
+        } else {
+            Some(self.address)
+        }
+    }
+
+    pub fn generate_htlc_preimage() -> [u8; 32] {
+        // the preimage is the secret that unlocks a hash time-locked transfer, it must be unguessable
+        let mut preimage = [0x00; 32];
+        OsRng.fill_bytes(&mut preimage);
+        preimage
+    }
+
+    fn create_data_tx(&mut self, version: u8, amount: u64, fee: u64, recipient: [u8; BLOCK_ADDRESS_SIZE], data: TxData, tx_format: TxFormat) -> Option<Transaction> {
+        // get the signature for the transaction, transactions carrying data must be at least DATA_TRANSACTION_VERSION, some types of data need a later version
+        let version = tx_format.get_version(version);
+        let tx_sig = Self::create_tx_sig(self, &TxMetadata::new(version, amount, fee, recipient, self.nonce).with_data(data).with_sender(self.address).with_chain_id(tx_format.get_chain_id()), tx_format)?;
+
+        // convert vector into [u8; COMPRESSED_PUBLIC_KEY_SIZE]
+        let sender_pub_key_vec = self.get_public_key().to_sec1_bytes().to_vec();
+        let sender_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = sender_pub_key_vec.try_into().unwrap();
+
+        Some(Transaction::new(version, amount, fee, recipient, sender_pub_key, tx_sig, self.nonce).with_data(data))
+    }
+
+    pub fn create_batch_tx(&mut self, outputs: Vec<([u8; BLOCK_ADDRESS_SIZE], u64)>, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // a batch transaction is sent to the BATCH_RECIPIENT, its amount is the total paid to its outputs
+        let amount = outputs.iter().try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))?;
+
+        // get the signature for the transaction, batch transactions must be at least BATCH_TRANSACTION_VERSION
+        let version = tx_format.get_version(*BATCH_TRANSACTION_VERSION);
+        let tx_sig = Self::create_tx_sig(self, &TxMetadata::new(version, amount, fee, *BATCH_RECIPIENT, self.nonce).with_sender(self.address).with_outputs(outputs.clone()).with_chain_id(tx_format.get_chain_id()), tx_format)?;
+
+        // convert vector into [u8; COMPRESSED_PUBLIC_KEY_SIZE]
+        let sender_pub_key_vec = self.get_public_key().to_sec1_bytes().to_vec();
+        let sender_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = sender_pub_key_vec.try_into().unwrap();
+
+        Some(Transaction::new(version, amount, fee, *BATCH_RECIPIENT, sender_pub_key, tx_sig, self.nonce).with_outputs(outputs))
+    }
+
+    pub fn create_replacement_tx(&self, transaction: &Transaction, fee: u64, tx_format: TxFormat) -> Option<Transaction> {
+        // only the wallet's own pending transactions can be replaced, a multisig transaction needs the signatures of the other key holders
+        let sender_pub_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = self.get_public_key().to_sec1_bytes().to_vec().try_into().ok()?;
+        if transaction.sender != sender_pub_key || transaction.multisig.is_some() {
+            return None
+        }
+
+        // the replacement is the same transaction with a higher fee, signed again
+        let mut replacement = transaction.clone();
+        replacement.fee = fee;
+        replacement.signature = Self::create_tx_sig(self, &replacement.get_tx_metadata(self.address, tx_format.get_chain_id()), tx_format)?;
+
+        Some(replacement)
+    }
+
+    pub fn create_multisig_tx(&self, amount: u64, fee: u64, recipient: [u8; BLOCK_ADDRESS_SIZE], nonce: u64, multisig: Multisig, tx_format: TxFormat) -> Option<Transaction> {
+        // the creator of a multisig transaction signs it first, the other key holders add their signatures with sign_multisig_tx
+        let version = tx_format.get_version(*MULTISIG_TRANSACTION_VERSION);
+        let tx_sig = Self::create_ecdsa_tx_sig(self, &TxMetadata::new(version, amount, fee, recipient, nonce).with_sender(multisig.get_address()).with_chain_id(tx_format.get_chain_id()))?;
+
+        self.sign_multisig_tx(&Transaction::new(version, amount, fee, recipient, *MULTISIG_SENDER, Signature::Ecdsa(tx_sig), nonce).with_multisig(multisig), tx_format.get_chain_id())
+    }
+
+    pub fn sign_multisig_tx(&self, transaction: &Transaction, chain_id: [u8; 32]) -> Option<Transaction> {
+        let mut multisig = transaction.multisig.clone()?;
+
+        // the wallet's public key must be one of the multisig account's keys
+        let public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = self.get_public_key().to_sec1_bytes().to_vec().try_into().ok()?;
+        let index = multisig.get_public_keys().iter().position(|multisig_public_key| *multisig_public_key == public_key)?;
+
+        // multisig accounts are always signed with ECDSA
+        let tx_sig = Self::create_ecdsa_tx_sig(self, &transaction.get_tx_metadata(multisig.get_address(), chain_id))?;
+        multisig.add_signature(index as u8, tx_sig);
+
+        // the transaction signature is always the first multisig signature
+        let mut transaction = transaction.clone();
+        transaction.signature = Signature::Ecdsa(multisig.get_signatures()[0].1);
+        transaction.multisig = Some(multisig);
+
+        Some(transaction)
+    }
+
+    fn create_tx_sig(&self, tx_metadata: &TxMetadata, tx_format: TxFormat) -> Option<Signature> {
+        // transactions of SCHNORR_TRANSACTION_VERSION or later are signed with Schnorr once the schnorr deployment is active, earlier versions can only be signed with ECDSA
+        if tx_metadata.version < *SCHNORR_TRANSACTION_VERSION || !tx_format.is_schnorr_active() {
+            return Self::create_ecdsa_tx_sig(self, tx_metadata).map(Signature::Ecdsa)
+        }
+
         // obtain the wallet signing key from the wallet file
         let signing_key = match self.get_signing_key() {
             Some(signing_key) => signing_key,
@@ -163,12 +422,41 @@ impl Wallet {
         };
 
         // serialize and hash the transaction metadata
-        let hashed_serialized_tx_metadata = TxMetadata::serialize_hash_tx_metadata(&TxMetadata::new(version, amount, fee, recipient, nonce));
+        let hashed_serialized_tx_metadata = TxMetadata::serialize_hash_tx_metadata(tx_metadata);
+
+        Self::sign_schnorr(signing_key, &hashed_serialized_tx_metadata).map(Signature::Schnorr)
+    }
+
+    fn create_ecdsa_tx_sig(&self, tx_metadata: &TxMetadata) -> Option<ecdsa::Signature> {
+        // obtain the wallet signing key from the wallet file
+        let signing_key = match self.get_signing_key() {
+            Some(signing_key) => signing_key,
+            None => {
+                println!("Failed to create transaction, could not obtain signing key");
+                return None
+            }
+        };
+
+        // serialize and hash the transaction metadata
+        let hashed_serialized_tx_metadata = TxMetadata::serialize_hash_tx_metadata(tx_metadata);
 
         Some(Self::sign(signing_key, &hashed_serialized_tx_metadata))
     }
 
-    pub fn create_block_sig(&self, version: u32, prev_hash: [u8; 32], timestamp: u64, transactions: &Vec<Transaction>) -> Option<Signature> {
+    pub fn create_vrf_proof(&self, alpha: &[u8]) -> Option<[u8; VRF_PROOF_SIZE]> {
+        // obtain the wallet signing key from the wallet file
+        let signing_key = match self.get_signing_key() {
+            Some(signing_key) => signing_key,
+            None => {
+                println!("Failed to create vrf proof, could not obtain signing key");
+                return None
+            }
+        };
+
+        vrf::prove(&signing_key, alpha)
+    }
+
+    pub fn create_block_sig(&self, version: u32, prev_hash: [u8; 32], timestamp: u64, vrf_proof: [u8; VRF_PROOF_SIZE], transactions: &Vec<Transaction>, chain_id: [u8; 32]) -> Option<Signature> {
         // obtain the wallet signing key from the wallet file
         let signing_key = match self.get_signing_key() {
             Some(signing_key) => signing_key,
@@ -182,26 +470,76 @@ impl Wallet {
         let merkle_root:[u8; 32] = BlockHeader::calculate_merkle_root(transactions.clone()).try_into().unwrap();
 
         // create the block header
-        let block_header = BlockHeader::new(version, prev_hash, merkle_root, timestamp);
+        let block_header = BlockHeader::new(version, prev_hash, merkle_root, timestamp, vrf_proof);
+
+        // serialize and hash block header, together with the chain id for versions that sign it
+        let hashed_serialized_block_header = block_header.serialize_hash_block_signing_payload(chain_id);
+
+        // blocks of SCHNORR_BLOCK_VERSION or later are signed with Schnorr, earlier versions can only be signed with ECDSA
+        if block_header.get_format_version() >= *SCHNORR_BLOCK_VERSION {
+            Self::sign_schnorr(signing_key, &hashed_serialized_block_header).map(Signature::Schnorr)
+        } else {
+            Some(Signature::Ecdsa(Self::sign(signing_key, &hashed_serialized_block_header)))
+        }
+    }
+
+    pub fn create_attestation(&self, block_height: u64, block_hash: [u8; 32]) -> Option<Attestation> {
+        // obtain the wallet signing key from the wallet file
+        let signing_key = match self.get_signing_key() {
+            Some(signing_key) => signing_key,
+            None => {
+                println!("Failed to create attestation, could not obtain signing key");
+                return None
+            }
+        };
+
+        let public_key: [u8; COMPRESSED_PUBLIC_KEY_SIZE] = signing_key.verifying_key().to_encoded_point(true).as_bytes().try_into().ok()?;
+
+        // sign the serialized and hashed block height and block hash
+        let signature = Self::sign(signing_key, &Attestation::serialize_hash_attestation_metadata(block_height, block_hash));
 
-        // serialize and hash block header
-        let hashed_serialized_block_header = block_header.serialize_hash_block_header();
+        Some(Attestation::new(block_height, block_hash, public_key, signature))
+    }
 
-        Some(Self::sign(signing_key, &hashed_serialized_block_header))
+    fn sign(signing_key: SigningKey, message: &[u8]) -> ecdsa::Signature {
+        // signatures must have a low S value to be valid
+        let signature: ecdsa::Signature = signing_key.sign(message);
+        signature.normalize_s().unwrap_or(signature)
     }
 
-    fn sign(signing_key: SigningKey, message: &[u8]) -> Signature {
-        signing_key.sign(message)
+    fn sign_schnorr(signing_key: SigningKey, message: &[u8]) -> Option<schnorr::Signature> {
+        // the Schnorr key is the same private key, its public key is the x coordinate of the wallet's public key so the address stays the same
+        let schnorr_signing_key = schnorr::SigningKey::from(*signing_key.as_nonzero_scalar());
+
+        // BIP340 mixes auxiliary randomness into the nonce to protect against side channel attacks
+        let mut aux_rand = [0x00; 32];
+        OsRng.fill_bytes(&mut aux_rand);
+
+        schnorr_signing_key.sign_raw(message, &aux_rand).ok()
     }
 
     pub fn generate_address(public_key: &elliptic_curve::PublicKey<Secp256k1>, compressed: bool) -> [u8; BLOCK_ADDRESS_SIZE] {
         // block addresses are generated in a similar way to version 1 bitcoin addresses
         // the general process can be found here: https://en.bitcoin.it/wiki/Technical_background_of_version_1_Bitcoin_addresses#How_to_create_Bitcoin_Address
+        // convert to [u8] array in comprsesed format with 0x02 or 0x03 prefix (if y is even/odd) or not compressed
+        Self::generate_address_from_bytes(public_key.to_encoded_point(compressed).as_bytes())
+    }
+
+    pub fn generate_multisig_address(threshold: u8, public_keys: &[[u8; COMPRESSED_PUBLIC_KEY_SIZE]]) -> [u8; BLOCK_ADDRESS_SIZE] {
+        // multisig addresses are generated like other addresses, but from the threshold followed by the sorted public keys instead of a single public key
+        let mut public_keys = public_keys.to_vec();
+        public_keys.sort();
 
+        let mut multisig_bytes = vec![threshold];
+        public_keys.iter().for_each(|public_key| multisig_bytes.extend_from_slice(public_key));
+
+        Self::generate_address_from_bytes(&multisig_bytes)
+    }
+
+    fn generate_address_from_bytes(bytes: &[u8]) -> [u8; BLOCK_ADDRESS_SIZE] {
         // sha256(compressed public key)
         let mut sha256_hasher: Sha256 = Sha256::new();
-        // convert to [u8] array in comprsesed format with 0x02 or 0x03 prefix (if y is even/odd) or not compressed
-        sha256_hasher.update(public_key.to_encoded_point(compressed));
+        sha256_hasher.update(bytes);
         let sha256_pub_key = sha256_hasher.finalize();
 
         // ripemd160(sha256(compressed public key))
@@ -302,6 +640,13 @@ impl Wallet {
         // reverse the checksum vector so it is in the correct order
         decoded_private_key_checksum.reverse();
 
+        // confirm what is left is the version byte(s), the private key and the compressed public key byte if there is one
+        let compressed_bytes_length = if compressed { WIF_VERSION1_COMPRESSED_BYTES.len() } else { 0 };
+        if decoded_private_key.len() != WIF_VERSION1_PREFIX_BYTES.len() + 32 + compressed_bytes_length {
+            println!("Invalid private key length");
+            return None
+        }
+
         // check the verion byte
         if decoded_private_key[0].eq(&WIF_VERSION1_PREFIX_BYTES[0]) {
             // remove the version byte(s)
@@ -372,7 +717,7 @@ impl Wallet {
 
         // obtain the private key fro mthe wallet file
         let private_key = match Self::read_wallet_file(wallet_file, self.config.get_compressed_public_key()) {
-            Some((private_key, _)) => private_key,
+            Some((private_key, _, _)) => private_key,
             None => {
                 println!("Failed to obtain signing key from wallet file");
                 return None
@@ -395,7 +740,7 @@ impl Wallet {
 
         // obtain the private key from the wallet file
         let private_key = match Self::read_wallet_file(wallet_file, self.config.get_compressed_public_key()) {
-            Some((private_key, _)) => private_key,
+            Some((private_key, _, _)) => private_key,
             None => {
                 println!("Failed to obtain signing key from wallet file");
                 return None
@@ -424,7 +769,7 @@ impl Wallet {
         let nonce: u64 = 0;
 
         // write private key and nonce to wallet file
-        Self::write_to_wallet_file(&wallet_file, &private_key, nonce, compressed, wallet_file_version)?;
+        Self::write_to_wallet_file(&wallet_file, &private_key, nonce, None, compressed, wallet_file_version)?;
 
         Ok(wallet_file)
     }
@@ -453,8 +798,8 @@ impl Wallet {
         Some(wallet_file)
     }
     
-    fn read_wallet_file(wallet_file: File, compressed: bool) -> Option<(SecretKey<Secp256k1>, u64)> {
-        // read wallet file and return a tuple of the private key and the nonce
+    fn read_wallet_file(wallet_file: File, compressed: bool) -> Option<(SecretKey<Secp256k1>, u64, Option<[u8; BLOCK_ADDRESS_SIZE]>)> {
+        // read wallet file and return a tuple of the private key, the nonce and the account address of a rotated key
         // if nonce cannot be obtained it will default to 0
 
         // read wallet file
@@ -489,8 +834,10 @@ impl Wallet {
 
         let private_key: SecretKey<Secp256k1>;
         let nonce: u64;
+        let mut account_address: Option<[u8; BLOCK_ADDRESS_SIZE]> = None;
 
-        if version == 1 {
+        // version 2 wallet files are version 1 wallet files followed by the address of the account the key has been rotated to
+        if version == 1 || version == 2 {
             // get private key string from wallet file fail else
             let wif_private_key_string = match wallet_file_string_parts.get(1) {
                 Some(wif_private_key_string) => wif_private_key_string,
@@ -526,23 +873,37 @@ impl Wallet {
                     0
                 }
             };
+
+            if version == 2 {
+                // get the account address from wallet file, the key can't be used without it
+                account_address = match wallet_file_string_parts.get(3).and_then(|account_address| account_address.as_bytes().try_into().ok()) {
+                    Some(account_address) => Some(account_address),
+                    None => {
+                        println!("Error obtaining account address from wallet file");
+                        return None
+                    }
+                };
+            }
         } else {
             // if unknown wallet file versoin obtained
             println!("Unknown wallet file version obtained");
             return None
         }
 
-        Some((private_key, nonce))
+        Some((private_key, nonce, account_address))
 
     }
 
-    fn write_to_wallet_file(mut wallet_file: &File, private_key: &elliptic_curve::SecretKey<Secp256k1>, nonce: u64, compressed: bool, wallet_file_version: u64) -> Result<(), io::Error> {
+    fn write_to_wallet_file(mut wallet_file: &File, private_key: &elliptic_curve::SecretKey<Secp256k1>, nonce: u64, account_address: Option<[u8; BLOCK_ADDRESS_SIZE]>, compressed: bool, wallet_file_version: u64) -> Result<(), io::Error> {
         // create wallet file buffer
         let mut wallet_file_buffer:Vec<u8> = vec![];
 
         // generate Wallet Import Format (wif) private key
         let wif_private_key = Self::generate_wif_private_key(&private_key, compressed);
 
+        // a rotated key is written to a version 2 wallet file so the account address can be stored with it
+        let wallet_file_version = if account_address.is_some() { 2 } else { wallet_file_version };
+
         // append wallet_file_version, wif private key and nonce to wallet file
         wallet_file_buffer.extend_from_slice(wallet_file_version.to_string().as_bytes());
         wallet_file_buffer.push(b'\n');
@@ -550,6 +911,12 @@ impl Wallet {
         wallet_file_buffer.push(b'\n');
         wallet_file_buffer.extend_from_slice(&nonce.to_string().as_bytes());
 
+        // append the account address of a rotated key
+        if let Some(account_address) = account_address {
+            wallet_file_buffer.push(b'\n');
+            wallet_file_buffer.extend_from_slice(&account_address);
+        }
+
         // write to wallet file
         let _ = match wallet_file.write(&wallet_file_buffer) {
             Ok(_) => (),
@@ -579,7 +946,7 @@ impl Wallet {
         };
 
         // write private key and nonce to wallet file
-        let _ = match Self::write_to_wallet_file(&wallet_file, &private_key, self.get_nonce(), self.config.get_compressed_public_key(), self.config.get_wallet_file_version()) {
+        let _ = match Self::write_to_wallet_file(&wallet_file, &private_key, self.get_nonce(), self.get_rotated_account_address(), self.config.get_compressed_public_key(), self.config.get_wallet_file_version()) {
             Ok(_) => (),
             Err(_) => {
                 println!("Failed to update wallet file");